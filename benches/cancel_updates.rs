@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use litesim::prelude::*;
+
+/// Number of updates scheduled for the cancelled model.
+const TARGET_UPDATES: usize = 10;
+
+fn background_time(i: usize) -> Time {
+    Time::at_secs(i as f64)
+}
+
+fn target_time(i: usize) -> Time {
+    Time::at_secs(i as f64 * 10.0 + 0.5)
+}
+
+fn scheduler(background: usize) -> Scheduler<'static> {
+    let mut scheduler = Scheduler::new(Time::MIN);
+    for i in 0..background {
+        scheduler
+            .schedule_update(background_time(i), format!("model_{}", i % 100))
+            .unwrap();
+    }
+    for i in 0..TARGET_UPDATES {
+        scheduler.schedule_update(target_time(i), "target").unwrap();
+    }
+    scheduler
+}
+
+/// Schedule layout without a per-model index, which has to scan every bucket
+/// to find updates of cancelled model.
+fn unindexed(background: usize) -> BTreeMap<Time, Vec<String>> {
+    let mut scheduled: BTreeMap<Time, Vec<String>> = BTreeMap::new();
+    for i in 0..background {
+        scheduled
+            .entry(background_time(i))
+            .or_default()
+            .push(format!("model_{}", i % 100));
+    }
+    for i in 0..TARGET_UPDATES {
+        scheduled
+            .entry(target_time(i))
+            .or_default()
+            .push("target".to_string());
+    }
+    scheduled
+}
+
+fn cancel_updates(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cancel_updates");
+    for background in [1_000, 10_000, 100_000] {
+        group.bench_with_input(
+            BenchmarkId::new("indexed", background),
+            &background,
+            |b, &background| {
+                b.iter_batched(
+                    || scheduler(background),
+                    |mut scheduler| {
+                        scheduler.cancel_updates("target", None);
+                        scheduler
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("scan", background),
+            &background,
+            |b, &background| {
+                b.iter_batched(
+                    || unindexed(background),
+                    |mut scheduled| {
+                        for entries in scheduled.values_mut() {
+                            entries.retain(|it| it != "target");
+                        }
+                        scheduled.retain(|_, entries| !entries.is_empty());
+                        scheduled
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, cancel_updates);
+criterion_main!(benches);
@@ -8,12 +8,52 @@ mod mapping;
 mod model;
 mod util;
 
+/// Implements connector plumbing of a `Model` trait implementation.
+///
+/// Inside model methods that receive a `ModelCtx`, calls to connector stubs
+/// are rewritten into context calls. Supported call shapes are:
+/// - `self.output(value)` - emits `value` now,
+/// - `self.output(value, trigger)` - emits `value` at `trigger`,
+/// - `self.output_at(value, trigger)` - same as above, but time is required,
+/// - `self.signal()`, `self.signal(trigger)` and `self.signal_at(trigger)` for
+///   signal connectors.
+///
+/// `trigger` can be anything convertible into a `TimeTrigger` (e.g. `In(2.0)`
+/// or `At(time)`). Calls to input connectors schedule internal events in the
+/// same manner.
+///
+/// `#[input(priority = N)]` sets the priority of an input connector; events
+/// delivered to a model at the same time are applied to connectors with
+/// higher priority first. Default priority is `0`.
+///
+/// Methods that aren't part of the `Model` trait are moved into an inherent
+/// impl block as helpers. A method whose name is close to a `Model` method
+/// (e.g. `handle_updte`) is rejected as a likely typo unless it's marked with
+/// `#[helper]`.
+///
+/// `#[litesim_model(state)]` implements `state_snapshot` and
+/// `restore_state_snapshot` through `ModelState`, which requires the model to
+/// implement `Serialize` and `Deserialize` and litesim `serde` feature to be
+/// enabled.
+///
+/// Multiple implementations for the same model can be gated behind mutually
+/// exclusive `cfg` attributes; each generates its own connector lists and
+/// helper methods (see `feature_gated` example).
 #[proc_macro_attribute]
 pub fn litesim_model(
-    _attr: proc_macro::TokenStream,
+    attr: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let model: ModelTraitImpl = parse_macro_input!(input as ModelTraitImpl);
+    let mut model: ModelTraitImpl = parse_macro_input!(input as ModelTraitImpl);
+    let arguments = syn::meta::parser(|meta| {
+        if meta.path.is_ident("state") {
+            model.state = true;
+            Ok(())
+        } else {
+            Err(meta.error("unsupported litesim_model argument"))
+        }
+    });
+    parse_macro_input!(attr with arguments);
     model.into_token_stream().into()
 }
 
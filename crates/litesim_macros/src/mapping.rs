@@ -1,3 +1,5 @@
+use std::{cell::RefCell, collections::HashSet};
+
 use proc_macro2::{Ident, Span};
 use syn::{punctuated::Punctuated, spanned::Spanned, *};
 
@@ -72,10 +74,23 @@ impl RenameIdent {
                 *left = Box::new(self.process_expr(left));
                 *right = Box::new(self.process_expr(right));
             }
-            Expr::Call(ExprCall { func: expr, .. })
-            | Expr::Cast(ExprCast { expr, .. })
+            Expr::Call(ExprCall {
+                func: expr, args, ..
+            })
+            | Expr::MethodCall(ExprMethodCall {
+                receiver: expr,
+                args,
+                ..
+            }) => {
+                **expr = self.process_expr(expr);
+                *args = args.iter().map(|arg| self.process_expr(arg)).collect();
+            }
+            Expr::Index(ExprIndex { expr, index, .. }) => {
+                **expr = self.process_expr(expr);
+                **index = self.process_expr(index);
+            }
+            Expr::Cast(ExprCast { expr, .. })
             | Expr::Group(ExprGroup { expr, .. })
-            | Expr::Index(ExprIndex { expr, .. })
             | Expr::Let(ExprLet { expr, .. })
             | Expr::Paren(ExprParen { expr, .. })
             | Expr::Reference(ExprReference { expr, .. })
@@ -88,8 +103,7 @@ impl RenameIdent {
             | Expr::Unary(ExprUnary { expr, .. })
             | Expr::Return(ExprReturn {
                 expr: Some(expr), ..
-            })
-            | Expr::MethodCall(ExprMethodCall { receiver: expr, .. }) => {
+            }) => {
                 *expr = Box::new(self.process_expr(&expr));
             }
             Expr::ForLoop(ExprForLoop { expr, body, .. }) => {
@@ -112,7 +126,7 @@ impl RenameIdent {
                 *expr = Box::new(self.process_expr(&expr));
                 for Arm { guard, body, .. } in arms {
                     if let Some((_, guard_expr)) = guard {
-                        *expr = Box::new(self.process_expr(guard_expr));
+                        **guard_expr = self.process_expr(guard_expr);
                     }
                     *body = Box::new(self.process_expr(body));
                 }
@@ -181,6 +195,8 @@ pub struct OCMInfo {
 pub struct SelfConnectorMapper {
     pub receiver: Ident,
     pub methods: Vec<OCMInfo>,
+    /// Names of connector methods that were called in processed blocks.
+    pub used: RefCell<HashSet<String>>,
 }
 
 fn expr_is_ident(expr: &Expr, ident: &Ident) -> bool {
@@ -267,8 +283,11 @@ impl SelfConnectorMapper {
             | Expr::TryBlock(ExprTryBlock { block, .. }) => {
                 *block = self.process_block(block, ctx_name)?;
             }
+            Expr::Call(ExprCall { func, args, .. }) => {
+                **func = self.process_expr(func, ctx_name)?;
+                *args = self.process_args(args, ctx_name)?;
+            }
             Expr::Assign(ExprAssign { right: expr, .. })
-            | Expr::Call(ExprCall { func: expr, .. })
             | Expr::Cast(ExprCast { expr, .. })
             | Expr::Group(ExprGroup { expr, .. })
             | Expr::Index(ExprIndex { expr, .. })
@@ -307,7 +326,7 @@ impl SelfConnectorMapper {
                 *expr = Box::new(self.process_expr(&expr, ctx_name)?);
                 for Arm { guard, body, .. } in arms {
                     if let Some((_, guard_expr)) = guard {
-                        *expr = Box::new(self.process_expr(guard_expr, ctx_name)?);
+                        **guard_expr = self.process_expr(guard_expr, ctx_name)?;
                     }
                     *body = Box::new(self.process_expr(body, ctx_name)?);
                 }
@@ -346,11 +365,24 @@ impl SelfConnectorMapper {
                 } = call.clone();
 
                 if expr_is_ident(&*receiver, &self.receiver) {
-                    if let Some(info) = self
+                    let method_name = method.to_string();
+                    // exact names take precedence so connectors ending with
+                    // `_at` still work
+                    let found = self
                         .methods
                         .iter()
-                        .find(|m| m.in_name == method.to_string())
-                    {
+                        .find(|m| m.in_name == method_name)
+                        .map(|it| (it, false))
+                        .or_else(|| {
+                            let base = method_name.strip_suffix("_at")?;
+                            self.methods
+                                .iter()
+                                .find(|m| m.in_name == base)
+                                .map(|it| (it, true))
+                        });
+
+                    if let Some((info, explicit_time)) = found {
+                        self.used.borrow_mut().insert(info.in_name.clone());
                         let name = info.out_name.clone();
                         let mut skipped_args = 0;
 
@@ -361,6 +393,7 @@ impl SelfConnectorMapper {
                             let msg = args
                                 .first()
                                 .ok_or_else(|| Error::new(args.span(), "missing event argument"))?;
+                            let msg = self.process_expr(msg, ctx_name)?;
                             parse_quote!(::litesim::event::Event::new(#msg))
                         };
 
@@ -369,16 +402,26 @@ impl SelfConnectorMapper {
                         };
 
                         let time: Expr = if args.len() >= 1 + skipped_args {
-                            args[skipped_args].clone()
+                            let time = self.process_expr(&args[skipped_args], ctx_name)?;
+                            parse_quote!(::litesim::time::TimeTrigger::from(#time))
+                        } else if explicit_time {
+                            return Err(Error::new(
+                                args.span(),
+                                format!(
+                                    "missing time argument; {}_at expects a TimeTrigger",
+                                    info.in_name
+                                ),
+                            ));
                         } else {
                             parse_quote!(::litesim::time::TimeTrigger::Now)
                         };
 
                         if args.len() >= 2 + skipped_args {
-                            let expected = if info.signal {
-                                "time can be optionally supplied"
-                            } else {
-                                "expected event and optionally time"
+                            let expected = match (info.signal, explicit_time) {
+                                (true, true) => "expected only time",
+                                (true, false) => "time can be optionally supplied",
+                                (false, true) => "expected event and time",
+                                (false, false) => "expected event and optionally time",
                             };
                             return Err(Error::new(
                                 args[skipped_args + 1].span(),
@@ -432,7 +475,7 @@ impl SelfConnectorMapper {
                     method,
                     turbofish,
                     paren_token,
-                    args,
+                    args: self.process_args(&args, ctx_name)?,
                 }));
             }
             _ => {}
@@ -440,4 +483,16 @@ impl SelfConnectorMapper {
 
         Ok(result)
     }
+
+    fn process_args(
+        &self,
+        args: &Punctuated<Expr, Token![,]>,
+        ctx_name: &Ident,
+    ) -> Result<Punctuated<Expr, Token![,]>> {
+        let mut mapped = Punctuated::new();
+        for arg in args {
+            mapped.push(self.process_expr(arg, ctx_name)?);
+        }
+        Ok(mapped)
+    }
 }
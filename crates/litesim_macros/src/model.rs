@@ -4,8 +4,8 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{
     parse::Parse, parse2, spanned::Spanned, token::Semi, Attribute, Block, Error, FnArg, Generics,
-    ImplItemFn, ItemImpl, LitStr, MacroDelimiter, Meta, MetaList, Pat, PatIdent, PatType, Path,
-    Receiver, Signature, Token, Type, TypePath, parse_quote,
+    ImplItemFn, ItemImpl, LitInt, LitStr, MacroDelimiter, Meta, MetaList, Pat, PatIdent, PatType, Path,
+    Receiver, Signature, Token, Type, TypeGroup, TypeParen, TypePath, TypeReference, parse_quote, Stmt, Expr,
 };
 
 use crate::{
@@ -37,58 +37,115 @@ pub fn except_self_attrib(attributes: impl AsRef<[Attribute]>) -> Vec<Attribute>
     result
 }
 
-pub fn pat_to_string(pattern: &Pat) -> Option<String> {
-    let result = match pattern {
-        Pat::Ident(PatIdent { ident, .. }) => ident.to_string(),
-        Pat::Wild(_) => "_".to_string(),
-        _ => return None,
-    };
+pub fn is_cfg_attrib(attribute: &Attribute) -> bool {
+    attribute.path().is_ident("cfg")
+}
 
-    Some(result)
+/// Whether attribute is `#[allow(unused)]`, which silences unused connector
+/// warnings.
+pub fn is_allow_unused_attrib(attribute: &Attribute) -> bool {
+    if !attribute.path().is_ident("allow") {
+        return false;
+    }
+    let mut result = false;
+    let _ = attribute.parse_nested_meta(|meta| {
+        if meta.path.is_ident("unused") {
+            result = true;
+        }
+        Ok(())
+    });
+    result
+}
+
+/// Splits attributes into `cfg` attributes and the rest.
+pub fn split_cfg_attribs(attributes: Vec<Attribute>) -> (Vec<Attribute>, Vec<Attribute>) {
+    attributes.into_iter().partition(is_cfg_attrib)
 }
 
 pub fn signal_ty() -> Type {
     parse_quote!(())
 }
 
+/// Marker attribute for context arguments whose type is an alias of
+/// `ModelCtx`.
+const CTX_MARKER: &str = "model_ctx";
+
+/// Checks whether the type is `ModelCtx` or a reference to it.
+pub fn is_model_ctx_ty(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(TypeReference { elem, .. })
+        | Type::Paren(TypeParen { elem, .. })
+        | Type::Group(TypeGroup { elem, .. }) => is_model_ctx_ty(elem),
+        Type::Path(TypePath { path, .. }) => path
+            .segments
+            .last()
+            .map(|seg| seg.ident == "ModelCtx")
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
+
+/// Checks whether the argument is a model context, either by its type or
+/// because it's marked with `#[model_ctx]`.
+pub fn is_ctx_arg(arg: &PatType) -> bool {
+    is_model_ctx_ty(&arg.ty) || arg.attrs.iter().any(|it| it.path().is_ident(CTX_MARKER))
+}
+
+/// Finds the model context argument and strips the `#[model_ctx]` marker from
+/// it.
 pub fn find_ctx_arg_mut(sig: &mut Signature) -> Option<&mut PatType> {
-    let mut found_ctx = None;
-
-    for input in &mut sig.inputs {
-        match input {
-            syn::FnArg::Typed(pat_t) => {
-                let ident_name = pat_to_string(&pat_t.pat)
-                    .expect("pat_to_string must support all find_ctx_name cases");
-
-                match &*pat_t.ty {
-                    Type::Path(TypePath { path, .. }) => {
-                        if path.segments.last().map(|seg| seg.ident == "ModelCtx") == Some(true) {
-                            return Some(pat_t);
-                        }
-                    }
-                    _ => {}
-                }
+    sig.inputs.iter_mut().find_map(|input| match input {
+        FnArg::Typed(pat_t) if is_ctx_arg(pat_t) => {
+            pat_t.attrs.retain(|it| !it.path().is_ident(CTX_MARKER));
+            Some(pat_t)
+        }
+        _ => None,
+    })
+}
 
-                match ident_name.as_str() {
-                    "model_context" | "model_ctx" => found_ctx = Some(pat_t),
-                    _ => {}
-                }
-            }
-            syn::FnArg::Receiver(_) => {}
+/// Returns the name of the context argument, naming it if it's a wildcard.
+pub fn ctx_arg_ident(arg: &mut PatType) -> Result<Ident, Error> {
+    match &*arg.pat {
+        Pat::Ident(PatIdent { ident, .. }) => Ok(ident.clone()),
+        Pat::Wild(_) => {
+            let ident = Ident::new("model_context_", Span::call_site());
+            *arg.pat = ident_to_pat(ident.clone());
+            Ok(ident)
         }
+        Pat::Struct(_) | Pat::TupleStruct(_) => Err(Error::new(
+            arg.span(),
+            "ModelCtx must not be destructured",
+        )),
+        _ => Err(Error::new(
+            arg.span(),
+            "unhandled ModelCtx name pattern; use literal or _",
+        )),
     }
+}
 
-    found_ctx
+/// Checks whether a block ends with a value or diverges.
+///
+/// Type of the value can't be checked during macro expansion, so this only
+/// catches bodies that evaluate to `()`.
+pub fn block_has_tail(block: &Block) -> bool {
+    matches!(
+        block.stmts.last(),
+        Some(Stmt::Expr(_, None))
+            | Some(Stmt::Expr(Expr::Return(_) | Expr::Macro(_) | Expr::Loop(_), _))
+            | Some(Stmt::Macro(_))
+    )
 }
 
 #[derive(Clone)]
 pub struct InputConnector {
     pub attributes: Vec<Attribute>,
+    pub cfg_attributes: Vec<Attribute>,
     pub name: Ident,
     pub event_name: Box<Pat>,
     pub event_ty: Box<Type>,
     pub ctx_name: Box<Pat>,
     pub signal: bool,
+    pub priority: i32,
     pub handler: Block,
 }
 
@@ -126,22 +183,30 @@ impl TryFrom<ItemConnector> for InputConnector {
             .rename
             .unwrap_or_else(|| sig.ident.to_string());
 
+        let (cfg_attributes, attributes) = split_cfg_attribs(value.attributes);
+
         Ok(InputConnector {
-            attributes: value.attributes,
+            attributes,
+            cfg_attributes,
             name: Ident::new(&name, sig.ident.span()),
             event_name,
             event_ty,
             ctx_name,
             signal: value.attrib_args.signal,
+            priority: value.attrib_args.priority.unwrap_or_default(),
             handler,
         })
     }
 }
 
 pub struct OutputConnector {
-    pub attributes: Vec<Attribute>,
+    pub cfg_attributes: Vec<Attribute>,
     pub name: Ident,
     pub ty: Box<Type>,
+    pub signal: bool,
+    pub allow_unused: bool,
+    /// Whether the connector is emitted from any of the model methods.
+    pub used: bool,
 }
 
 impl TryFrom<ItemConnector> for OutputConnector {
@@ -186,10 +251,15 @@ impl TryFrom<ItemConnector> for OutputConnector {
             .rename
             .unwrap_or_else(|| sig.ident.to_string());
 
+        let allow_unused = value.attributes.iter().any(is_allow_unused_attrib);
+
         Ok(OutputConnector {
-            attributes: value.attributes,
+            cfg_attributes: value.attributes.into_iter().filter(is_cfg_attrib).collect(),
             name: Ident::new(name.as_str(), sig.ident.span()),
             ty,
+            signal: value.attrib_args.signal,
+            allow_unused,
+            used: false,
         })
     }
 }
@@ -205,10 +275,55 @@ pub struct ModelTraitImpl {
     pub inputs: Vec<InputConnector>,
     pub outputs: Vec<OutputConnector>,
     pub other_impls: Vec<ImplItemFn>,
+    /// Methods that aren't part of the Model trait; they're moved into a
+    /// separate inherent impl block.
+    pub helpers: Vec<ImplItemFn>,
     pub unhandled: Vec<TokenStream>,
+    /// Whether state snapshots should be implemented through `ModelState`.
+    pub state: bool,
 }
 
-static AVOID_MANUAL_IMPL: &[&str] = &["type_id"];
+static AVOID_MANUAL_IMPL: &[&str] = &["type_id", "as_any", "as_any_mut"];
+
+/// Methods of Model trait; other methods in the impl block are treated as
+/// helpers.
+static MODEL_TRAIT_FNS: &[&str] = &[
+    "input_connectors",
+    "input_connector_infos",
+    "output_connectors",
+    "get_input_handler",
+    "input_model_type_id",
+    "init",
+    "handle_update",
+    "reset",
+    "state_snapshot",
+    "restore_state_snapshot",
+    "type_id",
+    "as_any",
+    "as_any_mut",
+];
+
+/// Model trait methods that receive a ModelCtx.
+static CTX_TRAIT_FNS: &[&str] = &["init", "handle_update", "reset"];
+
+/// Marker attribute for methods that should be treated as helpers even though
+/// their name resembles a Model trait method.
+const HELPER_MARKER: &str = "helper";
+
+/// Finds the Model trait method closest to `name`, if it's likely that `name`
+/// is a misspelling of it.
+///
+/// Allowed edit distance scales with name length so short helper names (e.g.
+/// `emit` or `wait`) aren't mistaken for `init`.
+fn closest_trait_fn(name: &str) -> Option<&'static str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    MODEL_TRAIT_FNS
+        .iter()
+        .map(|it| (*it, edit_distance(name, it)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(it, _)| it)
+}
 
 impl Parse for ModelTraitImpl {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
@@ -244,8 +359,10 @@ impl Parse for ModelTraitImpl {
         let mut details: Vec<ItemConnector> = Vec::with_capacity(implementation.items.len());
 
         let mut inputs = Vec::with_capacity(details.len());
-        let mut outputs = Vec::with_capacity(details.len());
+        let mut outputs: Vec<OutputConnector> = Vec::with_capacity(details.len());
+        let mut output_stub_names = Vec::with_capacity(details.len());
         let mut other_impls = Vec::with_capacity(details.len());
+        let mut helpers = Vec::new();
         let mut unhandled = Vec::with_capacity(details.len());
 
         for item in implementation.items {
@@ -271,6 +388,7 @@ impl Parse for ModelTraitImpl {
         let mut connector_mapper = SelfConnectorMapper {
             receiver: Ident::new("self", Span::call_site()),
             methods: Vec::with_capacity(details.len()),
+            used: Default::default(),
         };
 
         for out_fns in &details {
@@ -301,50 +419,13 @@ impl Parse for ModelTraitImpl {
                 Some(ConnectorKind::Input) => {
                     match &mut detail.item {
                         DetailContents::ItemFn(item_fn) => {
-                            let item_span = item_fn.span();
-                            let last_arg = item_fn.sig.inputs.last_mut().ok_or_else(|| {
-                                Error::new(item_span, "input connector missing arguments")
+                            let item_span = item_fn.sig.span();
+                            let ctx_arg = find_ctx_arg_mut(&mut item_fn.sig).ok_or_else(|| {
+                                Error::new(item_span, "input connector missing ModelCtx<'s> argument")
                             })?;
-
-                            let ctx_name = match last_arg {
-                                syn::FnArg::Typed(arg) => match &*arg.pat {
-                                    Pat::Ident(it) => Some(&it.ident),
-                                    Pat::Wild(_) => None,
-                                    Pat::Struct(_) => {
-                                        return Err(Error::new(
-                                            arg.span(),
-                                            "ModelCtx must not be destructured",
-                                        ))
-                                    }
-                                    _ => {
-                                        return Err(Error::new(
-                                            arg.span(),
-                                            "unhandled ModelCtx name pattern; use literal or _",
-                                        ))
-                                    }
-                                },
-                                _ => {
-                                    return Err(Error::new(
-                                        item_fn.span(),
-                                        "invalid last argument; expected a ModelCtx<'s> instead got self reference",
-                                    ))
-                                }
-                            };
-
-                            if let Some(ctx_name) = ctx_name {
-                                item_fn.block =
-                                    connector_mapper.process_block(&item_fn.block, ctx_name)?;
-                            } else {
-                                let wild_ident = Ident::new("model_context_", Span::call_site());
-                                item_fn.block =
-                                    connector_mapper.process_block(&item_fn.block, &wild_ident)?;
-                                match last_arg {
-                                    FnArg::Typed(PatType { pat, .. }) => {
-                                        *pat = Box::new(ident_to_pat(wild_ident))
-                                    }
-                                    FnArg::Receiver(_) => unreachable!(),
-                                }
-                            }
+                            let ctx_name = ctx_arg_ident(ctx_arg)?;
+                            item_fn.block =
+                                connector_mapper.process_block(&item_fn.block, &ctx_name)?;
                         }
                         DetailContents::Signature(_) => unreachable!("missing function body"),
                     };
@@ -352,6 +433,7 @@ impl Parse for ModelTraitImpl {
                     inputs.push(detail.try_into()?)
                 }
                 Some(ConnectorKind::Output) => {
+                    output_stub_names.push(detail.item.signature().ident.to_string());
                     outputs.push(detail.try_into()?);
                 }
                 None => {
@@ -363,6 +445,10 @@ impl Parse for ModelTraitImpl {
                     };
                     let name = item.sig.ident.to_string();
 
+                    let attr_count = item.attrs.len();
+                    item.attrs.retain(|it| !it.path().is_ident(HELPER_MARKER));
+                    let marked_helper = item.attrs.len() != attr_count;
+
                     if AVOID_MANUAL_IMPL.contains(&name.as_str()) {
                         return Err(Error::new(
                             item.sig.span(),
@@ -370,26 +456,51 @@ impl Parse for ModelTraitImpl {
                         ));
                     }
 
-                    if let Some(ctx_arg) = find_ctx_arg_mut(&mut item.sig) {
-                        match &mut *ctx_arg.pat {
-                            Pat::Ident(PatIdent { ident, .. }) => {
-                                item.block = connector_mapper.process_block(&item.block, ident)?;
-                            }
-                            Pat::Wild(_) => {
-                                let wild_ident = Ident::new("model_context_", Span::call_site());
-                                item.block =
-                                    connector_mapper.process_block(&item.block, &wild_ident)?;
-                                ctx_arg.pat = Box::new(ident_to_pat(wild_ident))
-                            }
-                            _ => unreachable!(),
+                    let item_span = item.sig.span();
+                    match find_ctx_arg_mut(&mut item.sig) {
+                        Some(ctx_arg) => {
+                            let ctx_name = ctx_arg_ident(ctx_arg)?;
+                            item.block = connector_mapper.process_block(&item.block, &ctx_name)?;
                         }
+                        None if CTX_TRAIT_FNS.contains(&name.as_str()) => {
+                            return Err(Error::new(
+                                item_span,
+                                format!("{} is missing ModelCtx<'s> argument", name),
+                            ));
+                        }
+                        None => {}
                     }
 
-                    other_impls.push(item)
+                    if MODEL_TRAIT_FNS.contains(&name.as_str()) {
+                        if marked_helper {
+                            return Err(Error::new(
+                                item_span,
+                                format!("{} is a Model method and can't be a helper", name),
+                            ));
+                        }
+                        other_impls.push(item)
+                    } else if marked_helper {
+                        helpers.push(item)
+                    } else if let Some(closest) = closest_trait_fn(&name) {
+                        return Err(Error::new(
+                            item.sig.ident.span(),
+                            format!(
+                                "{} is not a Model method; did you mean {}? Mark helper methods with #[{}]",
+                                name, closest, HELPER_MARKER
+                            ),
+                        ));
+                    } else {
+                        helpers.push(item)
+                    }
                 }
             }
         }
 
+        let used = connector_mapper.used.into_inner();
+        for (output, stub_name) in outputs.iter_mut().zip(output_stub_names) {
+            output.used = used.contains(&stub_name);
+        }
+
         Ok(ModelTraitImpl {
             attrs: implementation.attrs,
             impl_token: implementation.impl_token,
@@ -401,17 +512,81 @@ impl Parse for ModelTraitImpl {
             inputs,
             outputs,
             other_impls,
+            helpers,
             unhandled,
+            state: false,
         })
     }
 }
 
 impl ModelTraitImpl {
     pub fn gen_input_connectors(&self) -> TokenStream {
-        let inputs: Vec<_> = self.inputs.iter().map(|it| it.name.to_string()).collect();
+        let inputs = self.inputs.iter().map(|it| {
+            let cfg = &it.cfg_attributes;
+            let name = it.name.to_string();
+            quote! {
+                #(#cfg)*
+                result_.push(#name);
+            }
+        });
         quote! {
             fn input_connectors(&self) -> Vec<&'static str> {
-                vec![#(#inputs),*]
+                #[allow(unused_mut)]
+                let mut result_ = Vec::new();
+                #(#inputs)*
+                result_
+            }
+        }
+    }
+
+    pub fn gen_input_model_type_id(&self) -> TokenStream {
+        // generated handlers always handle Self, so only the number of inputs
+        // left after cfg attributes needs to be counted
+        let inputs = self.inputs.iter().map(|it| {
+            let cfg = &it.cfg_attributes;
+            quote! {
+                #(#cfg)*
+                {
+                    count_ += 1;
+                }
+            }
+        });
+        quote! {
+            fn input_model_type_id(&self, index_: usize) -> Option<std::any::TypeId> {
+                #[allow(unused_mut)]
+                let mut count_ = 0usize;
+                #(#inputs)*
+                (index_ < count_).then(|| ::litesim::prelude::const_type_id::<Self>())
+            }
+        }
+    }
+
+    pub fn gen_input_connector_infos(&self) -> TokenStream {
+        let infos: Vec<TokenStream> = self
+            .inputs
+            .iter()
+            .map(|input| {
+                let cfg = &input.cfg_attributes;
+                let ty = &input.event_ty;
+                let name = input.name.to_string();
+                let signal = input.signal;
+                let priority = input.priority;
+                quote! {
+                    #(#cfg)*
+                    result_.push(
+                        ::litesim::routes::InputConnectorInfo::new::<#ty>(#name)
+                            .with_signal(#signal)
+                            .with_priority(#priority)
+                    );
+                }
+            })
+            .collect();
+        quote! {
+            fn input_connector_infos(&self) -> Vec<::litesim::routes::InputConnectorInfo> {
+                #[allow(unused_mut)]
+                let mut result_ = Vec::new();
+                #(#infos)*
+                result_
             }
         }
     }
@@ -421,29 +596,74 @@ impl ModelTraitImpl {
             .outputs
             .iter()
             .map(|output| {
+                let cfg = &output.cfg_attributes;
                 let ty = &output.ty;
                 let name = output.name.to_string();
+                let signal = output.signal;
                 quote! {
-                    ::litesim::routes::OutputConnectorInfo::new::<#ty>(#name)
+                    #(#cfg)*
+                    result_.push(
+                        ::litesim::routes::OutputConnectorInfo::new::<#ty>(#name).with_signal(#signal)
+                    );
                 }
             })
             .collect();
         quote! {
             fn output_connectors(&self) -> Vec<OutputConnectorInfo> {
-                vec![#(#outputs),*]
+                #[allow(unused_mut)]
+                let mut result_ = Vec::new();
+                #(#outputs)*
+                result_
             }
         }
     }
 
+    /// Proc macros can't emit warnings on stable, so unused outputs are
+    /// reported by using a deprecated constant spanned to the connector name.
+    pub fn gen_unused_output_warnings(&self) -> TokenStream {
+        let warnings = self
+            .outputs
+            .iter()
+            .filter(|it| !it.used && !it.allow_unused)
+            .map(|it| {
+                let cfg = &it.cfg_attributes;
+                let note = format!(
+                    "output connector `{}` is declared but never emitted; check for typos or add #[allow(unused)] to it",
+                    it.name
+                );
+                let warning = Ident::new("unused_output_connector", it.name.span());
+                quote! {
+                    #(#cfg)*
+                    const _: () = {
+                        #[deprecated(note = #note)]
+                        #[allow(non_upper_case_globals)]
+                        const unused_output_connector: () = ();
+                        #warning
+                    };
+                }
+            });
+
+        quote! {
+            #(#warnings)*
+        }
+    }
+
     pub fn gen_input_handlers(&self) -> TokenStream {
         let mut handlers: Vec<TokenStream> = Vec::with_capacity(self.inputs.len());
 
         let model_type = &self.self_ty;
-        for (i, input) in self.inputs.iter().enumerate() {
+        for input in self.inputs.iter() {
+            let cfg = &input.cfg_attributes;
             let handler = InputHandler::new(model_type.clone(), input.clone());
 
+            // indices are counted at runtime as some connectors might be
+            // removed by cfg attributes
             handlers.push(quote! {
-                #i => #handler
+                #(#cfg)*
+                {
+                    if index_ == current_ #handler
+                    current_ += 1;
+                }
             })
         }
         quote! {
@@ -451,11 +671,37 @@ impl ModelTraitImpl {
             where
                 's: 'h,
             {
-                match index_ {
-                    #(#handlers),
-                    *
-                    _ => return None,
-                }
+                #[allow(unused_mut, unused_variables)]
+                let mut current_ = 0usize;
+                #(#handlers)*
+                None
+            }
+        }
+    }
+}
+
+impl ModelTraitImpl {
+    fn gen_state_snapshot(&self, other_fns: &[ImplItemFn]) -> TokenStream {
+        let manual = other_fns.iter().find(|it| {
+            it.sig.ident == "state_snapshot" || it.sig.ident == "restore_state_snapshot"
+        });
+        if let Some(manual) = manual {
+            return Error::new(manual.sig.ident.span(), "can't combine with state argument")
+                .to_compile_error();
+        }
+
+        quote! {
+            fn state_snapshot(
+                &self,
+            ) -> Result<::litesim::serde_json::Value, ::litesim::error::StateError> {
+                ::litesim::model::ModelState::state_json(self)
+            }
+
+            fn restore_state_snapshot(
+                &mut self,
+                state: ::litesim::serde_json::Value,
+            ) -> Result<(), ::litesim::error::StateError> {
+                ::litesim::model::ModelState::load_state(self, state)
             }
         }
     }
@@ -474,6 +720,8 @@ impl ToTokens for ModelTraitImpl {
         self.trait_path.to_tokens(tokens);
         tokens.extend(quote!(for));
         self.self_ty.to_tokens(tokens);
+        // Generics::to_tokens only emits parameters
+        self.generics.where_clause.to_tokens(tokens);
 
         let other_fns = &self.other_impls;
 
@@ -504,10 +752,24 @@ impl ToTokens for ModelTraitImpl {
             .map(|it| it.sig.ident.to_string())
             .any(|it| it == "input_connectors" || it == "get_input_handler");
 
+        let manual_input_infos_impl = other_fns
+            .iter()
+            .any(|it| it.sig.ident == "input_connector_infos");
+
+        let manual_input_type_impl = other_fns
+            .iter()
+            .any(|it| it.sig.ident == "input_model_type_id");
+
         let input_connectors: TokenStream =
             if !manual_inputs_impl {
                 let mut result = self.gen_input_connectors().to_token_stream();
+                if !manual_input_infos_impl {
+                    result.extend(self.gen_input_connector_infos().to_token_stream());
+                }
                 result.extend(self.gen_input_handlers().to_token_stream());
+                if !manual_input_type_impl {
+                    result.extend(self.gen_input_model_type_id().to_token_stream());
+                }
                 result
             } else {
                 if !self.inputs.is_empty() {
@@ -524,11 +786,18 @@ impl ToTokens for ModelTraitImpl {
                 }
             };
 
+        let state_impl = if self.state {
+            self.gen_state_snapshot(other_fns)
+        } else {
+            TokenStream::new()
+        };
+
         let unhandled = &self.unhandled;
 
         tokens.extend(quote!({
             #input_connectors
             #output_connectors
+            #state_impl
 
             #(#other_fns)*
             #(#unhandled)*
@@ -536,7 +805,29 @@ impl ToTokens for ModelTraitImpl {
             fn type_id(&self) -> std::any::TypeId {
                 ::litesim::prelude::const_type_id::<Self>()
             }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
         }));
+
+        if !self.helpers.is_empty() {
+            let generics = &self.generics;
+            let where_clause = &self.generics.where_clause;
+            let self_ty = &self.self_ty;
+            let helpers = &self.helpers;
+            tokens.extend(quote! {
+                impl #generics #self_ty #where_clause {
+                    #(#helpers)*
+                }
+            });
+        }
+
+        tokens.extend(self.gen_unused_output_warnings());
     }
 }
 
@@ -645,7 +936,11 @@ impl ItemConnector {
                             )),
                         },
                         match inputs.get(1) {
-                            Some(syn::FnArg::Typed(_)) => None,
+                            Some(syn::FnArg::Typed(arg)) if is_ctx_arg(arg) => None,
+                            Some(syn::FnArg::Typed(arg)) => Some(Error::new(
+                                arg.ty.span(),
+                                "expected ModelCtx<'s> second argument; mark aliased context types with #[model_ctx]",
+                            )),
                             Some(_) | None => Some(Error::new(
                                 ident.span(),
                                 "missing required ModelCtx<'s> second argument",
@@ -676,7 +971,11 @@ impl ItemConnector {
                             )),
                         },
                         match inputs.get(2) {
-                            Some(syn::FnArg::Typed(_)) => None,
+                            Some(syn::FnArg::Typed(arg)) if is_ctx_arg(arg) => None,
+                            Some(syn::FnArg::Typed(arg)) => Some(Error::new(
+                                arg.ty.span(),
+                                "expected ModelCtx<'s> third argument; mark aliased context types with #[model_ctx]",
+                            )),
                             Some(_) | None => Some(Error::new(
                                 inputs.span(),
                                 "missing required ModelCtx<'s> third argument",
@@ -759,14 +1058,39 @@ impl ItemConnector {
                         "only output connectors can be stub; inputs must have a body returning Result<(), SimulationError>",
                     ));
                 }
+                if let Some(block) = self.item.block() {
+                    if !block_has_tail(block) {
+                        signature_errors.push_back(Error::new(
+                            ident.span(),
+                            "input connector must return Result<(), SimulationError>",
+                        ));
+                    }
+                }
             }
             ConnectorKind::Output => {
-                if self.attributes.len() > 0 {
+                if self.attrib_args.sink {
                     signature_errors.push_back(Error::new(
-                        self.attributes.first().unwrap().span(),
+                        ident.span(),
+                        "only input connectors can be sinks",
+                    ));
+                }
+                if self.attrib_args.priority.is_some() {
+                    signature_errors.push_back(Error::new(
+                        ident.span(),
+                        "only input connectors can have a priority",
+                    ));
+                }
+                let erased: Vec<_> = self
+                    .attributes
+                    .iter()
+                    .filter(|it| !is_cfg_attrib(it) && !is_allow_unused_attrib(it))
+                    .collect();
+                if let Some(first) = erased.first() {
+                    signature_errors.push_back(Error::new(
+                        first.span(),
                         "output connectors aren't real functions; attribute will be erased",
                     ));
-                    for attr in self.attributes.iter().skip(1) {
+                    for attr in erased.iter().skip(1) {
                         signature_errors
                             .push_back(Error::new(attr.span(), "attribute will be erased"))
                     }
@@ -835,10 +1159,18 @@ impl TryFrom<ImplItemFn> for ItemConnector {
             }
         }
 
+        let attrib_args: ConnectorArguments = attrib_args.unwrap_or_default();
+        if connector_kind == Some(ConnectorKind::Input) && attrib_args.sink {
+            return Err(Error::new(
+                item.block.span(),
+                "sink input connectors must be stubs",
+            ));
+        }
+
         ItemConnector {
             kind: connector_kind,
             attributes: passed,
-            attrib_args: attrib_args.unwrap_or_default(),
+            attrib_args,
             item: DetailContents::ItemFn(item),
         }
         .validate()
@@ -857,15 +1189,14 @@ impl TryFrom<ItemFnStub> for ItemConnector {
             match ConnectorKind::try_from(a) {
                 Ok(kind) => {
                     connector_kind = Some(kind);
+                    if let Meta::List(MetaList { tokens, .. }) = &a.meta {
+                        attrib_args = Some(parse2(tokens.clone())?);
+                    }
                 }
                 _ => {
                     passed.push(a.clone());
                 }
             }
-
-            if let Meta::List(MetaList { tokens, .. }) = &a.meta {
-                attrib_args = Some(parse2(tokens.clone())?);
-            }
         }
 
         if connector_kind.is_none() {
@@ -875,16 +1206,40 @@ impl TryFrom<ItemFnStub> for ItemConnector {
             ));
         }
 
+        let attrib_args: ConnectorArguments = attrib_args.unwrap_or_default();
+        let item = if connector_kind == Some(ConnectorKind::Input) && attrib_args.sink {
+            DetailContents::ItemFn(sink_handler(item))
+        } else {
+            DetailContents::Signature(item)
+        };
+
         ItemConnector {
             kind: connector_kind,
             attributes: passed,
-            attrib_args: attrib_args.unwrap_or_default(),
-            item: DetailContents::Signature(item),
+            attrib_args,
+            item,
         }
         .validate()
     }
 }
 
+/// Turns an `#[input(sink)]` stub into a handler that ignores received events.
+fn sink_handler(stub: ItemFnStub) -> ImplItemFn {
+    let mut sig = stub.signature;
+    for arg in sig.inputs.iter_mut() {
+        if let FnArg::Typed(PatType { pat, .. }) = arg {
+            *pat = parse_quote!(_);
+        }
+    }
+    ImplItemFn {
+        attrs: stub.attrs,
+        vis: syn::Visibility::Inherited,
+        defaultness: None,
+        sig,
+        block: parse_quote!({ Ok(()) }),
+    }
+}
+
 pub struct ItemFnStub {
     pub attrs: Vec<Attribute>,
     pub signature: Signature,
@@ -904,7 +1259,11 @@ impl Parse for ItemFnStub {
 #[derive(Default)]
 pub struct ConnectorArguments {
     pub signal: bool,
+    /// Input stub that ignores received events.
+    pub sink: bool,
     pub rename: Option<String>,
+    /// Order of same-time events applied to the model; higher goes first.
+    pub priority: Option<i32>,
 }
 
 impl Parse for ConnectorArguments {
@@ -919,6 +1278,11 @@ impl Parse for ConnectorArguments {
                         let renamed = input.parse::<LitStr>()?;
                         result.rename = Some(renamed.value());
                     }
+                    "priority" => {
+                        let negative = input.parse::<Option<Token![-]>>()?.is_some();
+                        let value = input.parse::<LitInt>()?.base10_parse::<i32>()?;
+                        result.priority = Some(if negative { -value } else { value });
+                    }
                     _ => {
                         return Err(Error::new(name.span(), "unknown connector argument"));
                     }
@@ -929,6 +1293,9 @@ impl Parse for ConnectorArguments {
                     "signal" => {
                         result.signal = true;
                     }
+                    "sink" => {
+                        result.sink = true;
+                    }
                     _ => {
                         return Err(Error::new(flag.span(), "unknown connector flag"));
                     }
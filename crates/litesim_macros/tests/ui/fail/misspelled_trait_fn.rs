@@ -0,0 +1,12 @@
+use litesim::prelude::*;
+
+pub struct Ticker;
+
+#[litesim_model]
+impl<'s> Model<'s> for Ticker {
+    fn handle_updte(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        Ok(())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use litesim::prelude::*;
+
+pub struct Emitter;
+
+#[litesim_model]
+impl<'s> Model<'s> for Emitter {
+    #[output]
+    fn output(&self, value: u32) -> _;
+
+    #[input(signal)]
+    fn start(&mut self, _: ModelCtx<'s>) -> _ {
+        self.output(1, In(1.0), In(2.0))
+    }
+}
+
+fn main() {}
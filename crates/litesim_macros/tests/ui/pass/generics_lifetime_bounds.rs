@@ -0,0 +1,42 @@
+use std::marker::PhantomData;
+
+use litesim::prelude::*;
+
+pub struct Counter<M> {
+    count: usize,
+    _phantom: PhantomData<M>,
+}
+
+#[litesim_model]
+impl<'s, M> Model<'s> for Counter<M>
+where
+    M: Message + 's,
+    's: 'static,
+{
+    #[input]
+    fn input(&mut self, _value: M, _: ModelCtx<'s>) -> _ {
+        self.count += 1;
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut system = SystemModel::new();
+    system.push_model(
+        "counter",
+        Counter::<u8> {
+            count: 0,
+            _phantom: PhantomData,
+        },
+    );
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    for _ in 0..3 {
+        sim.schedule_event(0.0, Event::new(1u8), ConnectorPath::new("counter", "input"))
+            .unwrap();
+    }
+    sim.run().unwrap();
+    assert_eq!(
+        sim.inspect_model::<Counter<u8>>("counter").unwrap().count,
+        3
+    );
+}
@@ -0,0 +1,36 @@
+use std::fmt::Debug;
+
+use litesim::prelude::*;
+
+pub struct Logger<T> {
+    last: Option<T>,
+}
+
+#[litesim_model]
+impl<'s, T: Message + Clone + Debug + PartialEq> Model<'s> for Logger<T>
+where
+    T: Default,
+{
+    #[input]
+    fn input(&mut self, value: T, _: ModelCtx<'s>) -> _ {
+        if self.last.as_ref() != Some(&value) {
+            self.last = Some(value);
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut system = SystemModel::new();
+    system.push_model("logger", Logger::<String> { last: None });
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    sim.schedule_event(
+        0.0,
+        Event::new(String::from("a")),
+        ConnectorPath::new("logger", "input"),
+    )
+    .unwrap();
+    sim.run().unwrap();
+    let logger = sim.inspect_model::<Logger<String>>("logger").unwrap();
+    assert_eq!(format!("{:?}", logger.last), "Some(\"a\")");
+}
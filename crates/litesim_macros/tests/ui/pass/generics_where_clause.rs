@@ -0,0 +1,40 @@
+use litesim::prelude::*;
+
+pub struct Holder<T> {
+    value: T,
+}
+
+#[litesim_model]
+impl<'s, T> Model<'s> for Holder<T>
+where
+    T: Message + Clone + Default,
+{
+    #[input]
+    fn input(&mut self, value: T, _: ModelCtx<'s>) -> _ {
+        self.value = value;
+        self.output(self.value.clone())
+    }
+
+    #[output]
+    fn output(&self, value: T) -> _;
+
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.value = T::default();
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut system = SystemModel::new();
+    system.push_model("a", Holder { value: 0u32 });
+    system.push_model("b", Holder { value: 0u32 });
+    system.push_route(
+        ConnectorPath::new("a", "output"),
+        ConnectorPath::new("b", "input"),
+    );
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    sim.schedule_event(0.0, Event::new(4u32), ConnectorPath::new("a", "input"))
+        .unwrap();
+    sim.run().unwrap();
+    assert_eq!(sim.inspect_model::<Holder<u32>>("b").unwrap().value, 4);
+}
@@ -0,0 +1,36 @@
+use litesim::prelude::*;
+
+pub struct Ticker {
+    ticks: usize,
+}
+
+#[litesim_model]
+impl<'s> Model<'s> for Ticker {
+    #[output]
+    fn output(&self, value: usize) -> _;
+
+    #[input(signal)]
+    fn tick(&mut self, ctx: ModelCtx<'s>) -> _ {
+        self.emit_ticks(ctx)
+    }
+
+    #[helper]
+    fn handle_updates(&mut self) -> usize {
+        self.ticks
+    }
+
+    fn emit_ticks(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.ticks += 1;
+        self.output(self.handle_updates())
+    }
+}
+
+fn main() {
+    let mut system = SystemModel::new();
+    system.push_model("ticker", Ticker { ticks: 0 });
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    sim.schedule_event(0.0, Event::new(()), ConnectorPath::new("ticker", "tick"))
+        .unwrap();
+    sim.run().unwrap();
+    assert_eq!(sim.inspect_model::<Ticker>("ticker").unwrap().ticks, 1);
+}
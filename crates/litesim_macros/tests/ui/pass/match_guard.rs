@@ -0,0 +1,53 @@
+use litesim::prelude::*;
+
+pub struct Classifier {
+    threshold: u32,
+    small: usize,
+    large: usize,
+}
+
+#[litesim_model]
+impl<'s> Model<'s> for Classifier {
+    #[output]
+    fn large_value(&self, value: u32) -> _;
+
+    #[input]
+    fn input(&mut self, value: u32, _: ModelCtx<'s>) -> _ {
+        match value {
+            it if it < self.threshold => {
+                self.small += 1;
+                Ok(())
+            }
+            it => {
+                self.large += 1;
+                self.large_value(it)
+            }
+        }
+    }
+}
+
+fn main() {
+    let mut system = SystemModel::new();
+    system.push_model(
+        "classifier",
+        Classifier {
+            threshold: 10,
+            small: 0,
+            large: 0,
+        },
+    );
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    for value in [1u32, 20, 5, 30, 40] {
+        sim.schedule_event(
+            0.0,
+            Event::new(value),
+            ConnectorPath::new("classifier", "input"),
+        )
+        .unwrap();
+    }
+    sim.run().unwrap();
+
+    let classifier = sim.inspect_model::<Classifier>("classifier").unwrap();
+    assert_eq!(classifier.small, 2);
+    assert_eq!(classifier.large, 3);
+}
@@ -0,0 +1,81 @@
+use litesim::prelude::*;
+
+pub struct Emitter;
+
+#[litesim_model]
+impl<'s> Model<'s> for Emitter {
+    #[output]
+    fn output(&self, value: u32) -> _;
+
+    #[output(signal)]
+    fn done(&self) -> _;
+
+    #[input(signal)]
+    fn start(&mut self, _: ModelCtx<'s>) -> _ {
+        self.output(1)?;
+        self.output(2, In(1.0))?;
+        self.output_at(3, At(3.0))?;
+        self.done()?;
+        self.done(In(2.0))?;
+        self.done_at(At(4.0))
+    }
+}
+
+pub struct Recorder {
+    values: Vec<(Time, u32)>,
+    signals: Vec<Time>,
+}
+
+#[litesim_model]
+impl<'s> Model<'s> for Recorder {
+    #[input]
+    fn value(&mut self, value: u32, ctx: ModelCtx<'s>) -> _ {
+        self.values.push((ctx.time, value));
+        Ok(())
+    }
+
+    #[input(signal)]
+    fn signal(&mut self, ctx: ModelCtx<'s>) -> _ {
+        self.signals.push(ctx.time);
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut system = SystemModel::new();
+    system.push_model("emitter", Emitter);
+    system.push_model(
+        "recorder",
+        Recorder {
+            values: vec![],
+            signals: vec![],
+        },
+    );
+    system.push_route(
+        ConnectorPath::new("emitter", "output"),
+        ConnectorPath::new("recorder", "value"),
+    );
+    system.push_route(
+        ConnectorPath::new("emitter", "done"),
+        ConnectorPath::new("recorder", "signal"),
+    );
+
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    sim.schedule_event(0.0, Event::new(()), ConnectorPath::new("emitter", "start"))
+        .unwrap();
+    sim.run().unwrap();
+
+    let recorder = sim.inspect_model::<Recorder>("recorder").unwrap();
+    assert_eq!(
+        recorder.values,
+        vec![
+            (Time::at_secs(0.0), 1),
+            (Time::at_secs(1.0), 2),
+            (Time::at_secs(3.0), 3)
+        ]
+    );
+    assert_eq!(
+        recorder.signals,
+        vec![Time::at_secs(0.0), Time::at_secs(2.0), Time::at_secs(4.0)]
+    );
+}
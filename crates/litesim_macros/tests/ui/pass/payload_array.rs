@@ -0,0 +1,28 @@
+use litesim::prelude::*;
+
+pub struct Echo;
+
+#[litesim_model]
+impl<'s> Model<'s> for Echo {
+    #[input]
+    fn input(&mut self, value: [u8; 32], _: ModelCtx<'s>) -> _ {
+        self.output(value)
+    }
+
+    #[output]
+    fn output(&self, value: [u8; 32]) -> _;
+}
+
+fn main() {
+    let mut system = SystemModel::new();
+    system.push_model("a", Echo);
+    system.push_model("b", Echo);
+    system.push_route(
+        ConnectorPath::new("a", "output"),
+        ConnectorPath::new("b", "input"),
+    );
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    sim.schedule_event(0.0, Event::new([1u8; 32]), ConnectorPath::new("a", "input"))
+        .unwrap();
+    sim.run().unwrap();
+}
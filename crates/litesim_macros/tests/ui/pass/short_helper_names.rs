@@ -0,0 +1,43 @@
+use litesim::prelude::*;
+
+pub struct Relay {
+    sent: usize,
+}
+
+#[litesim_model]
+impl<'s> Model<'s> for Relay {
+    #[output]
+    fn output(&self, value: usize) -> _;
+
+    #[input]
+    fn input(&mut self, value: usize, ctx: ModelCtx<'s>) -> _ {
+        self.emit(value, ctx)
+    }
+
+    fn emit(&mut self, value: usize, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.sent += 1;
+        self.output(self.edit(value))
+    }
+
+    fn edit(&self, value: usize) -> usize {
+        value + self.wait()
+    }
+
+    fn wait(&self) -> usize {
+        self.sent
+    }
+}
+
+fn main() {
+    let mut system = SystemModel::new();
+    system.push_model("relay", Relay { sent: 0 });
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    sim.schedule_event(
+        0.0,
+        Event::new(1usize),
+        ConnectorPath::new("relay", "input"),
+    )
+    .unwrap();
+    sim.run().unwrap();
+    assert_eq!(sim.inspect_model::<Relay>("relay").unwrap().sent, 1);
+}
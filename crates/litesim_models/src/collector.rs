@@ -0,0 +1,41 @@
+use litesim::prelude::*;
+
+/// Terminal model that stores every received value along with the time it was
+/// received at.
+///
+/// After the simulation is run, collected values can be read through
+/// `Simulation::inspect_model::<Collector<T>>(id)`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Collector<T: Message> {
+    received: Vec<(Time, T)>,
+}
+
+impl<T: Message> Collector<T> {
+    pub fn new() -> Self {
+        Collector { received: vec![] }
+    }
+
+    pub fn received(&self) -> &[(Time, T)] {
+        &self.received
+    }
+}
+
+impl<T: Message> Default for Collector<T> {
+    fn default() -> Self {
+        Collector::new()
+    }
+}
+
+#[litesim_model]
+impl<'s, T: Message> Model<'s> for Collector<T> {
+    #[input]
+    fn input(&mut self, value: T, ctx: ModelCtx<'s>) -> _ {
+        self.received.push((ctx.time, value));
+        Ok(())
+    }
+
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.received.clear();
+        Ok(())
+    }
+}
@@ -11,6 +11,9 @@ pub struct Generator<
 > {
     pub(crate) generator: Option<Rng>,
     pub(crate) distribution: D,
+    /// Maximum number of values generated before the generator is exhausted.
+    pub max_count: Option<usize>,
+    pub(crate) emitted: usize,
     _phantom: PhantomData<T>,
 }
 
@@ -19,10 +22,25 @@ impl<T, Rng: SimulationRng, D: Distribution<T>> Generator<T, Rng, D> {
         Generator {
             generator,
             distribution,
+            max_count: None,
+            emitted: 0,
             _phantom: PhantomData,
         }
     }
 
+    pub fn with_max_count(mut self, max_count: usize) -> Self {
+        self.max_count = Some(max_count);
+        self
+    }
+
+    pub fn emitted(&self) -> usize {
+        self.emitted
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        matches!(self.max_count, Some(max) if self.emitted >= max)
+    }
+
     fn sample<'a>(&'a mut self, default: &'a RefCell<dyn SimulationRng>) -> T {
         match &mut self.generator {
             Some(overriden) => {
@@ -53,16 +71,35 @@ impl<'s, T: 'static, Rng: SimulationRng, D: Distribution<T> + 'static> Model<'s>
 {
     #[input(signal)]
     fn generate(&mut self, ctx: ModelCtx<'s>) -> _ {
-        ctx.schedule_update(Now)?;
+        if !self.is_exhausted() {
+            ctx.schedule_update(Now)?;
+        }
         Ok(())
     }
 
     #[output]
     fn output(&self, value: T) -> _;
 
+    #[output(signal)]
+    fn done(&self) -> _;
+
+    /// Clears the emitted count. An overriding RNG isn't reseeded and
+    /// continues from its current state.
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.emitted = 0;
+        Ok(())
+    }
+
     fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        if self.is_exhausted() {
+            return Ok(());
+        }
         let generated = self.sample(&ctx.rng);
         self.output(generated)?;
+        self.emitted += 1;
+        if self.is_exhausted() {
+            self.done()?;
+        }
         Ok(())
     }
 }
@@ -1,23 +1,58 @@
 #[cfg(feature = "cloner")]
 pub mod cloner;
+#[cfg(feature = "collector")]
+pub mod collector;
 #[cfg(any(feature = "rand", feature = "generator"))]
 pub mod generator;
+#[cfg(feature = "merge")]
+pub mod merge;
 #[cfg(feature = "queue")]
 pub mod queue;
+#[cfg(feature = "serde")]
+pub mod registry;
+#[cfg(feature = "router")]
+pub mod router;
+#[cfg(feature = "sampler")]
+pub mod sampler;
+#[cfg(feature = "throttle")]
+pub mod throttle;
 #[cfg(feature = "timer")]
 pub mod timer;
+#[cfg(feature = "weighted_router")]
+pub mod weighted_router;
 
+/// Re-exports of all enabled models, suffixed with `Model`.
+///
+/// Every model is exported under the same name regardless of enabled
+/// features; e.g. `GeneratorModel` is available whenever the generator module
+/// is, which is whenever `rand` is enabled.
 pub mod prelude {
     #[cfg(feature = "cloner")]
     pub use crate::cloner::Cloner as ClonerModel;
-    #[cfg(all(feature = "rand", not(feature = "generator")))]
-    pub use crate::generator::Generator;
-    #[cfg(all(feature = "rand", feature = "generator"))]
+    #[cfg(feature = "collector")]
+    pub use crate::collector::Collector as CollectorModel;
+    // generator module is available whenever rand is; only the Model impl
+    // requires the generator feature
+    #[cfg(any(feature = "rand", feature = "generator"))]
     pub use crate::generator::Generator as GeneratorModel;
+    #[cfg(feature = "merge")]
+    pub use crate::merge::Merge as MergeModel;
     #[cfg(feature = "queue")]
     pub use crate::queue::Queue as QueueModel;
+    #[cfg(feature = "router")]
+    pub use crate::router::Router as RouterModel;
+    #[cfg(feature = "sampler")]
+    pub use crate::sampler::Sampler as SamplerModel;
+    #[cfg(feature = "throttle")]
+    pub use crate::throttle::Throttle as ThrottleModel;
+    #[cfg(feature = "timer")]
+    pub use crate::timer::PayloadTimer as PayloadTimerModel;
+    #[cfg(all(feature = "timer", feature = "rand"))]
+    pub use crate::timer::RandomizedTimer as RandomizedTimerModel;
     #[cfg(feature = "timer")]
     pub use crate::timer::Timer as TimerModel;
+    #[cfg(feature = "weighted_router")]
+    pub use crate::weighted_router::WeightedRouter as WeightedRouterModel;
 
     pub use litesim::prelude as litesim;
 }
@@ -0,0 +1,51 @@
+use std::{borrow::Cow, marker::PhantomData};
+
+use litesim::prelude::*;
+
+type MergeHandler<'s, T> =
+    dyn Fn(&mut Merge<T>, Event<T>, ModelCtx<'s>) -> Result<(), SimulationError>;
+
+/// Forwards values received on any of `input_0..input_n` connectors to a
+/// single output.
+///
+/// Input connector names are allocated once during construction and leaked as
+/// [Model::input_connectors] requires `'static` names.
+pub struct Merge<T: Message> {
+    inputs: Vec<&'static str>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Message> Merge<T> {
+    pub fn new(inputs: usize) -> Self {
+        Merge {
+            inputs: (0..inputs)
+                .map(|i| &*Box::leak(format!("input_{}", i).into_boxed_str()))
+                .collect(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[litesim_model]
+impl<'s, T: Message> Model<'s> for Merge<T> {
+    // emitted from the handler closure below
+    #[allow(unused)]
+    #[output]
+    fn output(&self, value: T) -> _;
+
+    fn input_connectors(&self) -> Vec<&'static str> {
+        self.inputs.clone()
+    }
+
+    fn get_input_handler<'h>(&self, index: usize) -> Option<Box<dyn ErasedInputHandler<'h, 's>>>
+    where
+        's: 'h,
+    {
+        if index >= self.inputs.len() {
+            return None;
+        }
+        let handler: Box<&MergeHandler<'s, T>> =
+            Box::new(&|_, event, ctx| ctx.push_event(event, Cow::Borrowed("output")));
+        Some(handler)
+    }
+}
@@ -6,13 +6,48 @@ use litesim::prelude::*;
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Queue<T: Message> {
     queue: VecDeque<T>,
+    /// Maximum number of queued values; values received while the queue is
+    /// full are dropped and `overflow` signal is emitted instead.
+    pub capacity: Option<usize>,
+}
+
+impl<T: Message> Queue<T> {
+    pub fn new() -> Self {
+        Queue {
+            queue: VecDeque::new(),
+            capacity: None,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Queue {
+            queue: VecDeque::with_capacity(capacity),
+            capacity: Some(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
 }
 
 #[litesim_model]
 impl<'s, T: Message> Model<'s> for Queue<T> {
     #[input]
     fn input(&mut self, value: T, _: ModelCtx<'s>) -> _ {
+        if let Some(capacity) = self.capacity {
+            if self.queue.len() >= capacity {
+                self.overflow()?;
+                return Ok(());
+            }
+        }
         self.queue.push_front(value);
+        let length = self.queue.len();
+        self.length(length)?;
         Ok(())
     }
 
@@ -20,10 +55,23 @@ impl<'s, T: Message> Model<'s> for Queue<T> {
     fn pop(&mut self, _: ModelCtx<'s>) -> _ {
         if let Some(popped) = self.queue.pop_back() {
             self.output(popped)?;
+            let length = self.queue.len();
+            self.length(length)?;
         }
         Ok(())
     }
 
     #[output]
     fn output(&self, ev: T) -> _;
+
+    #[output(signal)]
+    fn overflow(&self) -> _;
+
+    #[output]
+    fn length(&self, count: usize) -> _;
+
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.queue.clear();
+        Ok(())
+    }
 }
@@ -0,0 +1,29 @@
+use litesim::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Registers built-in models that aren't generic under their type names.
+#[allow(unused_variables)]
+pub fn register_models(registry: &mut ModelRegistry<'_>) {
+    #[cfg(feature = "timer")]
+    registry.register::<crate::timer::Timer>("Timer");
+}
+
+/// Registers built-in models carrying messages of type `T` under names like
+/// `Queue<{name}>`.
+#[allow(unused_variables)]
+pub fn register_message_models<T>(registry: &mut ModelRegistry<'_>, name: &str)
+where
+    T: Message + Clone + Serialize + DeserializeOwned,
+{
+    #[cfg(feature = "collector")]
+    registry.register::<crate::collector::Collector<T>>(format!("Collector<{}>", name));
+    #[cfg(feature = "queue")]
+    registry.register::<crate::queue::Queue<T>>(format!("Queue<{}>", name));
+    #[cfg(feature = "sampler")]
+    registry.register::<crate::sampler::Sampler<T>>(format!("Sampler<{}>", name));
+    #[cfg(feature = "throttle")]
+    registry.register::<crate::throttle::Throttle<T>>(format!("Throttle<{}>", name));
+    #[cfg(feature = "weighted_router")]
+    registry
+        .register::<crate::weighted_router::WeightedRouter<T>>(format!("WeightedRouter<{}>", name));
+}
@@ -0,0 +1,44 @@
+use std::borrow::Cow;
+
+use litesim::prelude::*;
+
+/// Routes each received value to one of `output_0..output_n` connectors
+/// selected by a predicate.
+///
+/// Values for which the predicate returns an index that's out of range are
+/// dropped, same as values sent to an unconnected output.
+///
+/// Router doesn't support serde as predicate closures can't be serialized.
+pub struct Router<T: Message> {
+    outputs: usize,
+    predicate: Box<dyn Fn(&T) -> usize>,
+}
+
+impl<T: Message> Router<T> {
+    pub fn new(outputs: usize, predicate: impl Fn(&T) -> usize + 'static) -> Self {
+        Router {
+            outputs,
+            predicate: Box::new(predicate),
+        }
+    }
+}
+
+#[litesim_model]
+impl<'s, T: Message> Model<'s> for Router<T> {
+    #[input]
+    fn input(&mut self, value: T, ctx: ModelCtx<'s>) -> _ {
+        let target = (self.predicate)(&value);
+        if target < self.outputs {
+            ctx.push_event(Event::new(value), Cow::Owned(format!("output_{}", target)))?;
+        }
+        Ok(())
+    }
+
+    fn output_connectors(&self) -> Vec<OutputConnectorInfo> {
+        let mut result = Vec::with_capacity(self.outputs);
+        for i in 0..self.outputs {
+            result.push(OutputConnectorInfo::new::<T>(format!("output_{}", i)))
+        }
+        result
+    }
+}
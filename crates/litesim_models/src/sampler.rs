@@ -0,0 +1,76 @@
+use litesim::prelude::*;
+
+/// Error returned when constructing a [Sampler] with an invalid period.
+#[derive(Debug, thiserror::Error)]
+pub enum SamplerError {
+    #[error("Sampling period {0} must be greater than zero")]
+    InvalidPeriod(TimeDelta),
+}
+
+/// Zero-order hold; stores the latest received value and emits it every
+/// `period`.
+///
+/// Nothing is emitted until a value is received, unless an initial value is
+/// provided.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sampler<T: Message + Clone> {
+    pub period: TimeDelta,
+    value: Option<T>,
+    /// Value restored on reset.
+    initial: Option<T>,
+}
+
+impl<T: Message + Clone> Sampler<T> {
+    pub fn new(period: impl Into<TimeDelta>) -> Result<Self, SamplerError> {
+        let period = period.into();
+        if period <= TimeDelta::ZERO {
+            return Err(SamplerError::InvalidPeriod(period));
+        }
+        Ok(Sampler {
+            period,
+            value: None,
+            initial: None,
+        })
+    }
+
+    pub fn with_initial(mut self, value: T) -> Self {
+        self.value = Some(value.clone());
+        self.initial = Some(value);
+        self
+    }
+
+    /// Returns the value that will be emitted on next sample.
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+}
+
+#[litesim_model]
+impl<'s, T: Message + Clone> Model<'s> for Sampler<T> {
+    #[input]
+    fn input(&mut self, value: T, _: ModelCtx<'s>) -> _ {
+        self.value = Some(value);
+        Ok(())
+    }
+
+    #[output]
+    fn output(&self, value: T) -> _;
+
+    fn init(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        ctx.schedule_update(In(self.period))?;
+        Ok(())
+    }
+
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.value = self.initial.clone();
+        Ok(())
+    }
+
+    fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        if let Some(value) = self.value.clone() {
+            self.output(value)?;
+        }
+        ctx.schedule_update(In(self.period))?;
+        Ok(())
+    }
+}
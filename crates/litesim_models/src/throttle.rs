@@ -0,0 +1,98 @@
+use litesim::prelude::*;
+
+/// What [Throttle] does with values received before `min_interval` elapses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ThrottlePolicy {
+    /// Excess values are dropped.
+    #[default]
+    Drop,
+    /// Latest excess value is kept and emitted as soon as the interval
+    /// elapses; older buffered values are dropped.
+    Buffer,
+}
+
+/// Forwards at most one value per `min_interval`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Throttle<T: Message> {
+    pub min_interval: TimeDelta,
+    pub policy: ThrottlePolicy,
+    last_emission: Option<Time>,
+    buffered: Option<T>,
+    dropped: usize,
+}
+
+impl<T: Message> Throttle<T> {
+    pub fn new(min_interval: impl Into<TimeDelta>) -> Self {
+        Throttle {
+            min_interval: min_interval.into(),
+            policy: ThrottlePolicy::Drop,
+            last_emission: None,
+            buffered: None,
+            dropped: 0,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: ThrottlePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Number of values that were dropped.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    fn next_allowed(&self) -> Option<Time> {
+        self.last_emission.map(|last| last + self.min_interval)
+    }
+}
+
+#[litesim_model]
+impl<'s, T: Message> Model<'s> for Throttle<T> {
+    #[input]
+    fn input(&mut self, value: T, ctx: ModelCtx<'s>) -> _ {
+        let next_allowed = self.next_allowed();
+        match next_allowed {
+            Some(next) if ctx.time < next => match self.policy {
+                ThrottlePolicy::Drop => self.dropped += 1,
+                ThrottlePolicy::Buffer => {
+                    if self.buffered.replace(value).is_some() {
+                        self.dropped += 1;
+                    } else {
+                        ctx.schedule_update(At(next))?;
+                    }
+                }
+            },
+            // an update is already scheduled for this time and will emit the
+            // buffered value, so emitting here would exceed the interval
+            _ if self.buffered.is_some() => {
+                self.buffered = Some(value);
+                self.dropped += 1;
+            }
+            _ => {
+                self.last_emission = Some(ctx.time);
+                self.output(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[output]
+    fn output(&self, value: T) -> _;
+
+    fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        if let Some(value) = self.buffered.take() {
+            self.last_emission = Some(ctx.time);
+            self.output(value)?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.last_emission = None;
+        self.buffered = None;
+        self.dropped = 0;
+        Ok(())
+    }
+}
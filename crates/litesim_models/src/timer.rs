@@ -2,6 +2,19 @@ use std::ops::Bound;
 
 use litesim::prelude::*;
 
+/// Returns the time of the first fire or `None` if it falls outside the limits.
+///
+/// The first fire is `delay` after the start limit (or `current` time if
+/// unbounded) and, like repeats, must be included in the limits; so an
+/// excluded start limit without a delay never fires.
+fn initial_fire(limits: &TimeBounds, delay: Option<TimeDelta>, current: Time) -> Option<Time> {
+    let start = match limits.start {
+        Bound::Included(limit) | Bound::Excluded(limit) => limit,
+        Bound::Unbounded => current,
+    };
+    limits.clamp_trigger(In(delay.unwrap_or(TimeDelta::ZERO)), start)
+}
+
 #[derive(Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Timer {
@@ -16,20 +29,7 @@ impl<'s> Model<'s> for Timer {
     fn signal(&self) -> _;
 
     fn init(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
-        let initial = match self.limits.start {
-            Bound::Excluded(limit) => At(limit),
-            Bound::Included(limit) => At(limit),
-            Bound::Unbounded => Now,
-        }
-        .to_discrete(ctx.time)
-            + self.delay.unwrap_or(TimeDelta::MIN);
-
-        let overshoot_initial = match self.limits.end {
-            Bound::Excluded(limit) => initial > limit,
-            Bound::Included(limit) => initial >= limit,
-            Bound::Unbounded => false,
-        };
-        if !overshoot_initial {
+        if let Some(initial) = initial_fire(&self.limits, self.delay, ctx.time) {
             ctx.schedule_update(At(initial))?;
         }
         Ok(())
@@ -37,16 +37,70 @@ impl<'s> Model<'s> for Timer {
 
     fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
         self.signal()?;
-        if let Some(repeat) = self.repeat {
-            let next_time = ctx.time + repeat;
-            let overshoot_next = match self.limits.end {
-                Bound::Excluded(limit) => next_time > limit,
-                Bound::Included(limit) => next_time >= limit,
-                Bound::Unbounded => false,
-            };
-            if !overshoot_next {
-                ctx.schedule_update(In(repeat))?;
-            }
+        if let Some(next) = self
+            .repeat
+            .and_then(|repeat| self.limits.clamp_trigger(In(repeat), ctx.time))
+        {
+            ctx.schedule_update(At(next))?;
+        }
+        Ok(())
+    }
+}
+
+/// Timer that emits a value produced by `payload` on each fire instead of a
+/// signal.
+///
+/// `payload` is called with the index of the fire and the time it occured at.
+pub struct PayloadTimer<T: Message> {
+    pub limits: TimeBounds,
+    pub delay: Option<TimeDelta>,
+    pub repeat: Option<TimeDelta>,
+    fired: usize,
+    payload: Box<dyn Fn(usize, Time) -> T>,
+}
+
+impl<T: Message> PayloadTimer<T> {
+    pub fn new(payload: impl Fn(usize, Time) -> T + 'static) -> Self {
+        PayloadTimer {
+            limits: TimeBounds::default(),
+            delay: None,
+            repeat: None,
+            fired: 0,
+            payload: Box::new(payload),
+        }
+    }
+
+    pub fn fired(&self) -> usize {
+        self.fired
+    }
+}
+
+#[litesim_model]
+impl<'s, T: Message> Model<'s> for PayloadTimer<T> {
+    #[output]
+    fn tick(&self, value: T) -> _;
+
+    fn init(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        if let Some(initial) = initial_fire(&self.limits, self.delay, ctx.time) {
+            ctx.schedule_update(At(initial))?;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.fired = 0;
+        Ok(())
+    }
+
+    fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        let value = (self.payload)(self.fired, ctx.time);
+        self.fired += 1;
+        self.tick(value)?;
+        if let Some(next) = self
+            .repeat
+            .and_then(|repeat| self.limits.clamp_trigger(In(repeat), ctx.time))
+        {
+            ctx.schedule_update(At(next))?;
         }
         Ok(())
     }
@@ -54,7 +108,7 @@ impl<'s> Model<'s> for Timer {
 
 #[cfg(feature = "rand")]
 mod randomized {
-    use std::{cell::RefCell, ops::Bound};
+    use std::cell::RefCell;
 
     use crate::generator::Generator;
     use litesim::prelude::*;
@@ -65,6 +119,7 @@ mod randomized {
     #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct RandomizedTimer<Rng: SimulationRng, D: Distribution<TimeDelta> + 'static> {
         pub limits: TimeBounds,
+        /// Enables repetition; each repeat interval is sampled from `generator`.
         pub repeat: Option<TimeDelta>,
         pub generator: Generator<TimeDelta, Rng, D>,
     }
@@ -91,43 +146,24 @@ mod randomized {
         fn signal(&self) -> _;
 
         fn init(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
-            let initial = match self.limits.start {
-                Bound::Excluded(limit) => At(limit),
-                Bound::Included(limit) => At(limit),
-                Bound::Unbounded => Now,
-            };
-
             let added = self.sample_delay(&ctx.rng);
-
-            let initial_discrete = initial.clone().to_discrete(ctx.time) + added;
-
-            let overshoot_initial = match self.limits.end {
-                Bound::Excluded(limit) => initial_discrete > limit,
-                Bound::Included(limit) => initial_discrete >= limit,
-                Bound::Unbounded => false,
-            };
-            if !overshoot_initial {
-                ctx.schedule_update(initial)?;
+            if let Some(initial) = super::initial_fire(&self.limits, Some(added), ctx.time) {
+                ctx.schedule_update(At(initial))?;
             }
             Ok(())
         }
 
         fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
             self.signal()?;
-            if let Some(repeat) = self.repeat {
-                let next_time = ctx.time + self.sample_delay(&ctx.rng);
-
-                let overshoot_next = match self.limits.end {
-                    Bound::Excluded(limit) => next_time > limit,
-                    Bound::Included(limit) => next_time >= limit,
-                    Bound::Unbounded => false,
-                };
-                if !overshoot_next {
-                    ctx.schedule_update(In(repeat))?;
+            if self.repeat.is_some() {
+                let delay = self.sample_delay(&ctx.rng);
+                if let Some(next) = self.limits.clamp_trigger(In(delay), ctx.time) {
+                    ctx.schedule_update(At(next))?;
                 }
             }
             Ok(())
         }
     }
 }
+#[cfg(feature = "rand")]
 pub use randomized::*;
@@ -0,0 +1,130 @@
+use std::{borrow::Cow, marker::PhantomData};
+
+use litesim::prelude::*;
+
+/// Error returned when constructing a [WeightedRouter] from invalid weights.
+#[derive(Debug, thiserror::Error)]
+pub enum WeightError {
+    #[error("Weight {weight} at index {index} isn't a finite non-negative number or overflows the total")]
+    InvalidWeight { index: usize, weight: f64 },
+    #[error("At least one weight must be greater than zero")]
+    AllZero,
+}
+
+/// Routes each received value to one of `output_0..output_n` connectors
+/// chosen randomly according to provided weights.
+///
+/// Weights are normalized, so they don't need to sum up to 1. Serialized
+/// routers are represented by their list of weights.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<f64>", into = "Vec<f64>")
+)]
+pub struct WeightedRouter<T: Message + Clone> {
+    cumulative: Vec<f64>,
+    total: f64,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Message + Clone> WeightedRouter<T> {
+    pub fn new(weights: Vec<f64>) -> Result<Self, WeightError> {
+        if let Some((index, weight)) = weights
+            .iter()
+            .enumerate()
+            .find(|(_, it)| !it.is_finite() || **it < 0.0)
+        {
+            return Err(WeightError::InvalidWeight {
+                index,
+                weight: *weight,
+            });
+        }
+
+        let mut acc = 0.0;
+        let cumulative: Vec<f64> = weights
+            .iter()
+            .map(|it| {
+                acc += it;
+                acc
+            })
+            .collect();
+        if let Some(index) = cumulative.iter().position(|it| !it.is_finite()) {
+            return Err(WeightError::InvalidWeight {
+                index,
+                weight: weights[index],
+            });
+        }
+        let total = acc;
+        if total <= 0.0 {
+            return Err(WeightError::AllZero);
+        }
+
+        Ok(WeightedRouter {
+            cumulative,
+            total,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns weights the router was constructed with.
+    pub fn weights(&self) -> Vec<f64> {
+        let mut previous = 0.0;
+        self.cumulative
+            .iter()
+            .map(|it| {
+                let weight = it - previous;
+                previous = *it;
+                weight
+            })
+            .collect()
+    }
+}
+
+impl<T: Message + Clone> Clone for WeightedRouter<T> {
+    fn clone(&self) -> Self {
+        WeightedRouter {
+            cumulative: self.cumulative.clone(),
+            total: self.total,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: Message + Clone> TryFrom<Vec<f64>> for WeightedRouter<T> {
+    type Error = WeightError;
+
+    fn try_from(weights: Vec<f64>) -> Result<Self, Self::Error> {
+        WeightedRouter::new(weights)
+    }
+}
+
+impl<T: Message + Clone> From<WeightedRouter<T>> for Vec<f64> {
+    fn from(router: WeightedRouter<T>) -> Self {
+        router.weights()
+    }
+}
+
+#[litesim_model]
+impl<'s, T: Message + Clone> Model<'s> for WeightedRouter<T> {
+    #[input]
+    fn input(&mut self, value: T, ctx: ModelCtx<'s>) -> _ {
+        let total = self.total;
+        let last = self.cumulative.len() - 1;
+        let sample: f64 = ctx.rand_range(0.0..total);
+        let target = self
+            .cumulative
+            .iter()
+            .position(|it| sample < *it)
+            .unwrap_or(last);
+        ctx.push_event(Event::new(value), Cow::Owned(format!("output_{}", target)))?;
+        Ok(())
+    }
+
+    fn output_connectors(&self) -> Vec<OutputConnectorInfo> {
+        let mut result = Vec::with_capacity(self.cumulative.len());
+        for i in 0..self.cumulative.len() {
+            result.push(OutputConnectorInfo::new::<T>(format!("output_{}", i)))
+        }
+        result
+    }
+}
@@ -0,0 +1,24 @@
+#![cfg(any(feature = "rand", feature = "generator"))]
+//! Checks `GeneratorModel` is exported under every feature combination that
+//! enables the generator module; run with `--no-default-features` and each of
+//! `rand`, `generator` and `rand,generator`.
+
+use litesim_models::prelude::*;
+use rand::{distributions::Uniform, rngs::StdRng, SeedableRng};
+
+fn generator() -> GeneratorModel<u32, StdRng, Uniform<u32>> {
+    GeneratorModel::new(Some(StdRng::seed_from_u64(0)), Uniform::new(1, 10))
+}
+
+#[test]
+fn generator_model_is_exported() {
+    let _ = generator();
+}
+
+#[cfg(feature = "generator")]
+#[test]
+fn generator_model_is_a_model() {
+    let mut system = litesim::SystemModel::new();
+    system.push_model("generator", generator());
+    assert!(litesim::Simulation::new(StdRng::seed_from_u64(0), system, 0.0).is_ok());
+}
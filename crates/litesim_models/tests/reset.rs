@@ -0,0 +1,94 @@
+#![cfg(all(
+    feature = "generator",
+    feature = "sampler",
+    feature = "collector",
+    feature = "timer"
+))]
+
+use std::ops::Bound;
+
+use litesim::prelude::*;
+use litesim_models::{collector::Collector, generator::Generator, sampler::Sampler, timer::Timer};
+use rand::{distributions::Uniform, rngs::StdRng, rngs::ThreadRng, SeedableRng};
+
+type Values = Generator<u32, ThreadRng, Uniform<u32>>;
+
+#[derive(Debug, PartialEq)]
+struct RunResult {
+    received: Vec<(Time, u32)>,
+    emitted: usize,
+    held: Option<u32>,
+}
+
+fn run(sim: &mut Simulation<'static>) -> RunResult {
+    sim.set_rng(StdRng::seed_from_u64(3));
+    sim.run_until(Time::at_secs(10.0)).unwrap();
+    RunResult {
+        received: sim
+            .inspect_model::<Collector<u32>>("collector")
+            .unwrap()
+            .received()
+            .to_vec(),
+        emitted: sim.inspect_model::<Values>("generator").unwrap().emitted(),
+        held: sim
+            .inspect_model::<Sampler<u32>>("sampler")
+            .unwrap()
+            .value()
+            .copied(),
+    }
+}
+
+#[test]
+fn reset_run_matches_first_run() {
+    let mut system = SystemModel::new();
+    system.push_model(
+        "timer",
+        Timer {
+            limits: TimeBounds {
+                start: Bound::Included(Time::at_secs(1.0)),
+                end: Bound::Unbounded,
+            },
+            delay: None,
+            repeat: Some(TimeDelta::from_secs_f64(1.0)),
+        },
+    );
+    system.push_model(
+        "generator",
+        Values::new_shared(Uniform::new(1, 100)).with_max_count(3),
+    );
+    system.push_model("sampler", Sampler::<u32>::new(0.5).unwrap().with_initial(0));
+    system.push_model("collector", Collector::<u32>::new());
+    system.push_route(
+        ConnectorPath::new("timer", "signal"),
+        ConnectorPath::new("generator", "generate"),
+    );
+    system.push_route(
+        ConnectorPath::new("generator", "output"),
+        ConnectorPath::new("sampler", "input"),
+    );
+    system.push_route(
+        ConnectorPath::new("sampler", "output"),
+        ConnectorPath::new("collector", "input"),
+    );
+
+    let mut sim = Simulation::new(StdRng::seed_from_u64(3), system, Time::MIN).unwrap();
+    let first = run(&mut sim);
+    assert_eq!(first.emitted, 3);
+    assert_eq!(first.received.first().map(|it| it.1), Some(0));
+    assert_ne!(first.held, Some(0));
+
+    sim.reset().unwrap();
+    assert_eq!(
+        sim.inspect_model::<Values>("generator").unwrap().emitted(),
+        0
+    );
+    assert_eq!(
+        sim.inspect_model::<Sampler<u32>>("sampler")
+            .unwrap()
+            .value(),
+        Some(&0)
+    );
+
+    let second = run(&mut sim);
+    assert_eq!(first, second);
+}
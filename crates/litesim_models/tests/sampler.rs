@@ -0,0 +1,20 @@
+#![cfg(feature = "sampler")]
+
+use litesim::prelude::*;
+use litesim_models::sampler::{Sampler, SamplerError};
+
+#[test]
+fn rejects_non_positive_period() {
+    assert!(matches!(
+        Sampler::<u32>::new(0.0),
+        Err(SamplerError::InvalidPeriod(period)) if period == TimeDelta::ZERO
+    ));
+    assert!(matches!(
+        Sampler::<u32>::new(-1.0),
+        Err(SamplerError::InvalidPeriod(_))
+    ));
+    assert_eq!(
+        Sampler::<u32>::new(0.5).unwrap().period,
+        TimeDelta::from_secs_f64(0.5)
+    );
+}
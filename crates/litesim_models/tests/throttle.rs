@@ -0,0 +1,77 @@
+#![cfg(all(feature = "throttle", feature = "collector", feature = "rand"))]
+
+use litesim::prelude::*;
+use litesim_models::{
+    collector::Collector,
+    throttle::{Throttle, ThrottlePolicy},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn run_throttle(policy: ThrottlePolicy, inputs: &[(f64, u32)]) -> (Vec<(Time, u32)>, usize) {
+    let mut system = SystemModel::new();
+    system.push_model("throttle", Throttle::<u32>::new(1.0).with_policy(policy));
+    system.push_model("collector", Collector::<u32>::new());
+    system.push_route(
+        ConnectorPath::new("throttle", "output"),
+        ConnectorPath::new("collector", "input"),
+    );
+
+    let mut sim = Simulation::new(StdRng::seed_from_u64(0), system, Time::MIN).unwrap();
+    for (time, value) in inputs {
+        sim.schedule_event(
+            Time::at_secs(*time),
+            Event::new(*value),
+            ConnectorPath::new("throttle", "input"),
+        )
+        .unwrap();
+    }
+    sim.run().unwrap();
+
+    let received = sim
+        .inspect_model::<Collector<u32>>("collector")
+        .unwrap()
+        .received()
+        .to_vec();
+    let dropped = sim
+        .inspect_model::<Throttle<u32>>("throttle")
+        .unwrap()
+        .dropped();
+    (received, dropped)
+}
+
+fn at(values: &[(f64, u32)]) -> Vec<(Time, u32)> {
+    values
+        .iter()
+        .map(|(time, value)| (Time::at_secs(*time), *value))
+        .collect()
+}
+
+#[test]
+fn drop_policy_drops_values_within_interval() {
+    let (received, dropped) = run_throttle(
+        ThrottlePolicy::Drop,
+        &[(0.0, 1), (0.5, 2), (1.0, 3), (1.5, 4)],
+    );
+    assert_eq!(received, at(&[(0.0, 1), (1.0, 3)]));
+    assert_eq!(dropped, 2);
+}
+
+#[test]
+fn buffer_policy_emits_latest_value_after_interval() {
+    let (received, dropped) = run_throttle(
+        ThrottlePolicy::Buffer,
+        &[(0.0, 1), (0.25, 2), (0.5, 3), (2.5, 4)],
+    );
+    assert_eq!(received, at(&[(0.0, 1), (1.0, 3), (2.5, 4)]));
+    assert_eq!(dropped, 1);
+}
+
+#[test]
+fn buffer_policy_input_at_window_boundary() {
+    let (received, dropped) = run_throttle(
+        ThrottlePolicy::Buffer,
+        &[(0.0, 1), (0.5, 2), (1.0, 3), (1.5, 4)],
+    );
+    assert_eq!(received, at(&[(0.0, 1), (1.0, 3), (2.0, 4)]));
+    assert_eq!(dropped, 1);
+}
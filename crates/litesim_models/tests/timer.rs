@@ -0,0 +1,163 @@
+#![cfg(all(feature = "timer", feature = "collector", feature = "rand"))]
+
+use std::ops::Bound;
+
+use litesim::prelude::*;
+use litesim_models::{
+    collector::Collector,
+    generator::Generator,
+    timer::{RandomizedTimer, Timer},
+};
+use rand::{distributions::Uniform, prelude::Distribution, rngs::StdRng, SeedableRng};
+
+/// Samples delays uniformly from a range of seconds.
+struct UniformDelay(Uniform<f64>);
+
+impl UniformDelay {
+    fn new(low: f64, high: f64) -> Self {
+        UniformDelay(Uniform::new(low, high))
+    }
+}
+
+impl Distribution<TimeDelta> for UniformDelay {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> TimeDelta {
+        TimeDelta::from_secs_f64(self.0.sample(rng))
+    }
+}
+
+fn collect_signals(system: &mut SystemModel<'static>, timer: &'static str) {
+    system.push_model("collector", Collector::<()>::new());
+    system.push_route(
+        ConnectorPath::new(timer, "signal"),
+        ConnectorPath::new("collector", "input"),
+    );
+}
+
+fn collected(sim: &Simulation<'static>) -> Vec<Time> {
+    sim.inspect_model::<Collector<()>>("collector")
+        .unwrap()
+        .received()
+        .iter()
+        .map(|(time, _)| *time)
+        .collect()
+}
+
+fn run_timer(limits: TimeBounds, delay: Option<f64>, repeat: Option<f64>, until: f64) -> Vec<Time> {
+    let mut system = SystemModel::new();
+    system.push_model(
+        "timer",
+        Timer {
+            limits,
+            delay: delay.map(TimeDelta::from_secs_f64),
+            repeat: repeat.map(TimeDelta::from_secs_f64),
+        },
+    );
+    collect_signals(&mut system, "timer");
+
+    let mut sim = Simulation::new(StdRng::seed_from_u64(0), system, Time::MIN).unwrap();
+    sim.run_until(Time::at_secs(until)).unwrap();
+    collected(&sim)
+}
+
+fn run_randomized(limits: TimeBounds, repeat: bool, until: f64) -> Vec<Time> {
+    let mut system = SystemModel::new();
+    system.push_model(
+        "timer",
+        RandomizedTimer {
+            limits,
+            repeat: repeat.then_some(TimeDelta::ZERO),
+            generator: Generator::new(Some(StdRng::seed_from_u64(7)), UniformDelay::new(1.0, 5.0)),
+        },
+    );
+    collect_signals(&mut system, "timer");
+
+    let mut sim = Simulation::new(StdRng::seed_from_u64(0), system, Time::MIN).unwrap();
+    sim.run_until(Time::at_secs(until)).unwrap();
+    collected(&sim)
+}
+
+fn inc(value: f64) -> Bound<Time> {
+    Bound::Included(Time::at_secs(value))
+}
+
+fn exc(value: f64) -> Bound<Time> {
+    Bound::Excluded(Time::at_secs(value))
+}
+
+#[test]
+fn timer_first_fire_at_start_bound() {
+    let cases = [
+        (inc(2.0), Bound::Unbounded, None, vec![2.0]),
+        (exc(2.0), Bound::Unbounded, None, vec![]),
+        (exc(2.0), Bound::Unbounded, Some(0.5), vec![2.5]),
+        (inc(2.0), inc(2.0), None, vec![2.0]),
+        (inc(2.0), exc(2.0), None, vec![]),
+        (exc(2.0), inc(2.0), None, vec![]),
+        (inc(2.0), exc(3.0), None, vec![2.0]),
+        (inc(2.0), exc(3.0), Some(1.0), vec![]),
+        (inc(2.0), inc(3.0), Some(1.0), vec![3.0]),
+        (Bound::Unbounded, inc(1.0), None, vec![0.0]),
+    ];
+    for (start, end, delay, expected) in cases {
+        let limits = TimeBounds { start, end };
+        let expected: Vec<Time> = expected.into_iter().map(Time::at_secs).collect();
+        assert_eq!(
+            run_timer(limits, delay, None, 10.0),
+            expected,
+            "{:?} delayed by {:?}",
+            limits,
+            delay
+        );
+    }
+}
+
+#[test]
+fn timer_repeats_within_bounds() {
+    let limits = TimeBounds {
+        start: inc(1.0),
+        end: inc(3.0),
+    };
+    let expected: Vec<Time> = [1.0, 2.0, 3.0].into_iter().map(Time::at_secs).collect();
+    assert_eq!(run_timer(limits, None, Some(1.0), 10.0), expected);
+
+    let limits = TimeBounds {
+        start: inc(1.0),
+        end: exc(3.0),
+    };
+    let expected: Vec<Time> = [1.0, 2.0].into_iter().map(Time::at_secs).collect();
+    assert_eq!(run_timer(limits, None, Some(1.0), 10.0), expected);
+
+    let limits = TimeBounds {
+        start: exc(1.0),
+        end: inc(3.0),
+    };
+    let expected: Vec<Time> = [1.5, 2.5].into_iter().map(Time::at_secs).collect();
+    assert_eq!(run_timer(limits, Some(0.5), Some(1.0), 10.0), expected);
+}
+
+#[test]
+fn randomized_timer_intervals_vary() {
+    let fired = run_randomized(TimeBounds::default(), true, 100.0);
+    assert!(fired.len() > 3);
+
+    let intervals: Vec<TimeDelta> = fired.windows(2).map(|it| it[1] - it[0]).collect();
+    for interval in &intervals {
+        assert!(*interval >= TimeDelta::from_secs_f64(1.0));
+        assert!(*interval < TimeDelta::from_secs_f64(5.0));
+    }
+    assert!(intervals.windows(2).any(|it| it[0] != it[1]));
+}
+
+#[test]
+fn randomized_timer_first_fire_includes_sampled_delay() {
+    let start = Time::at_secs(10.0);
+    let limits = TimeBounds {
+        start: Bound::Included(start),
+        end: Bound::Unbounded,
+    };
+    let fired = run_randomized(limits, false, 100.0);
+
+    let sampled = UniformDelay::new(1.0, 5.0).sample(&mut StdRng::seed_from_u64(7));
+    assert!(sampled > TimeDelta::ZERO);
+    assert_eq!(fired, vec![start + sampled]);
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "weighted_router")]
+
+use litesim_models::weighted_router::{WeightError, WeightedRouter};
+
+#[test]
+fn rejects_invalid_weights() {
+    assert!(matches!(
+        WeightedRouter::<u32>::new(vec![1.0, -1.0]),
+        Err(WeightError::InvalidWeight { index: 1, .. })
+    ));
+    assert!(matches!(
+        WeightedRouter::<u32>::new(vec![f64::NAN]),
+        Err(WeightError::InvalidWeight { index: 0, .. })
+    ));
+    assert!(matches!(
+        WeightedRouter::<u32>::new(vec![f64::MAX, 1.0, f64::MAX]),
+        Err(WeightError::InvalidWeight { index: 2, .. })
+    ));
+    assert!(matches!(
+        WeightedRouter::<u32>::new(vec![0.0, 0.0]),
+        Err(WeightError::AllZero)
+    ));
+    assert!(matches!(
+        WeightedRouter::<u32>::new(vec![]),
+        Err(WeightError::AllZero)
+    ));
+    assert_eq!(
+        WeightedRouter::<u32>::new(vec![0.0, 2.0, 1.0])
+            .unwrap()
+            .weights(),
+        vec![0.0, 2.0, 1.0]
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn registry_rejects_invalid_weights() {
+    use litesim::prelude::*;
+    use litesim_models::registry::register_message_models;
+
+    let mut registry = ModelRegistry::new();
+    register_message_models::<u32>(&mut registry, "u32");
+
+    assert!(registry
+        .construct("WeightedRouter<u32>", serde_json::json!([1.0, 3.0]))
+        .is_ok());
+    assert!(matches!(
+        registry.construct("WeightedRouter<u32>", serde_json::json!([0.0])),
+        Err(RegistryError::InvalidConfig { .. })
+    ));
+    assert!(matches!(
+        registry.construct("WeightedRouter<u32>", serde_json::json!([-2.0, 1.0])),
+        Err(RegistryError::InvalidConfig { .. })
+    ));
+    assert!(matches!(
+        registry.construct(
+            "WeightedRouter<u32>",
+            serde_json::json!([f64::MAX, f64::MAX])
+        ),
+        Err(RegistryError::InvalidConfig { .. })
+    ));
+}
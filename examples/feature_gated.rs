@@ -0,0 +1,107 @@
+use litesim::prelude::*;
+
+/// Sensor that reports a slowly rising temperature.
+///
+/// With `rand` feature it adds noise to readings and additionally reports the
+/// noise through `noise` connector.
+pub struct Sensor {
+    base: f64,
+    samples: usize,
+}
+
+impl Sensor {
+    pub fn new(base: f64) -> Self {
+        Sensor { base, samples: 0 }
+    }
+}
+
+#[litesim_model]
+#[cfg(feature = "rand")]
+impl<'s> Model<'s> for Sensor {
+    #[output]
+    fn reading(&self, value: f64) -> _;
+    #[output]
+    fn noise(&self, value: f64) -> _;
+
+    fn init(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        ctx.schedule_update(Now)?;
+        Ok(())
+    }
+
+    fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        let noise: f64 = ctx.rand_range(-0.5..0.5);
+        let value = self.next_temperature() + noise;
+        self.reading(value)?;
+        self.noise(noise)?;
+        ctx.schedule_update(In(1.0))?;
+        Ok(())
+    }
+
+    fn next_temperature(&mut self) -> f64 {
+        self.samples += 1;
+        self.base + self.samples as f64 * 0.1
+    }
+}
+
+#[litesim_model]
+#[cfg(not(feature = "rand"))]
+impl<'s> Model<'s> for Sensor {
+    #[output]
+    fn reading(&self, value: f64) -> _;
+
+    fn init(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        ctx.schedule_update(Now)?;
+        Ok(())
+    }
+
+    fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        let value = self.next_temperature();
+        self.reading(value)?;
+        ctx.schedule_update(In(1.0))?;
+        Ok(())
+    }
+
+    fn next_temperature(&mut self) -> f64 {
+        self.samples += 1;
+        self.base + self.samples as f64 * 0.1
+    }
+}
+
+pub struct Display;
+
+#[litesim_model]
+impl<'s> Model<'s> for Display {
+    #[input]
+    fn reading(&mut self, value: f64, ctx: ModelCtx<'s>) -> _ {
+        log::info!("{}: {:.2} °C", ctx.time.format(1), value);
+        Ok(())
+    }
+
+    #[input]
+    fn noise(&mut self, value: f64, ctx: ModelCtx<'s>) -> _ {
+        log::info!("{}: noise {:+.2} °C", ctx.time.format(1), value);
+        Ok(())
+    }
+}
+
+fn main() {
+    env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .init();
+
+    let mut system = SystemModel::new();
+
+    system.push_model("sensor", Sensor::new(20.0));
+    system.push_model("display", Display);
+
+    system.push_route(connection!(sensor::reading), connection!(display::reading));
+    #[cfg(feature = "rand")]
+    system.push_route(connection!(sensor::noise), connection!(display::noise));
+
+    let mut sim = Simulation::builder()
+        .system(system)
+        .build()
+        .expect("invalid model");
+
+    sim.run_until(5.0).expect("simulation error");
+}
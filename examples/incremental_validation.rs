@@ -0,0 +1,54 @@
+use std::time::{Duration, Instant};
+
+use litesim::prelude::*;
+
+/// Forwards received values unchanged.
+pub struct Relay;
+
+#[litesim_model]
+impl<'s> Model<'s> for Relay {
+    #[input]
+    fn input(&mut self, value: u32, _: ModelCtx<'s>) -> _ {
+        self.output(value)
+    }
+
+    #[output]
+    fn output(&self, value: u32) -> _;
+}
+
+/// Builds a chain of `length` relays, validating the system after every
+/// pushed route with provided `validate` function.
+fn build_chain(
+    length: usize,
+    validate: impl Fn(&mut SystemModel<'static>) -> Result<(), ValidationError>,
+) -> Duration {
+    let mut system = SystemModel::with_capacity(length, length);
+    let start = Instant::now();
+    system.push_model("relay_0", Relay);
+    for i in 1..length {
+        system.push_model(format!("relay_{}", i), Relay);
+        system.push_route(
+            ConnectorPath::new(format!("relay_{}", i - 1), "output"),
+            ConnectorPath::new(format!("relay_{}", i), "input"),
+        );
+        validate(&mut system).expect("invalid model");
+    }
+    start.elapsed()
+}
+
+fn main() {
+    env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .init();
+
+    for length in [100, 500, 1000] {
+        let incremental = build_chain(length, SystemModel::validate);
+        let full = build_chain(length, SystemModel::validate_full);
+        log::info!(
+            "{} relays: incremental validation {:?}, full validation {:?}",
+            length,
+            incremental,
+            full
+        );
+    }
+}
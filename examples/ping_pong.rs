@@ -17,7 +17,7 @@ impl<'s> Model<'s> for Player {
         log::info!(
             "Player {} got the ball at {}",
             ctx.model_id.as_ref(),
-            ctx.time
+            ctx.time.format(3)
         );
         self.send(In(ctx.rand_range(0.0..1.0)))?;
         Ok(())
@@ -34,7 +34,7 @@ impl<'s> Model<'s> for Fish {
             log::info!(
                 "{} moved at {} because he was bumped",
                 ctx.model_id,
-                ctx.time
+                ctx.time.format(3)
             );
             self.was_bumped = false;
             self.bump_count += 1;
@@ -0,0 +1,424 @@
+use std::any::{Any, TypeId};
+
+use crate::{
+    error::{RoutingError, SimulationError, ValidationError},
+    event::ErasedEvent,
+    model::{ErasedInputHandler, Model, ModelImpl},
+    routes::{ConnectorPath, OutputConnectorInfo},
+    simulation::{ConnectorCtx, ModelCtx, Simulation},
+    system::SystemModel,
+    time::TimeTrigger,
+    util::{closest_match, CowStr},
+};
+
+/// Id of the model collecting events sent through exposed outputs.
+const OUTPUTS_ID: &str = "@outputs";
+
+/// Model wrapping a whole system, which exposes some of its connectors as
+/// its own.
+///
+/// Events received on external inputs are delivered to internal input
+/// connectors they're mapped to, and events sent through mapped internal
+/// output connectors are sent through external outputs. Inner system is
+/// simulated in lockstep with the outer one, so time of inner events matches
+/// time of the outer simulation.
+pub struct CompoundModel {
+    system: Option<SystemModel<'static>>,
+    simulation: Option<Simulation<'static>>,
+    inputs: Vec<ExternalInput>,
+    outputs: Vec<OutputConnectorInfo>,
+}
+
+struct ExternalInput {
+    name: &'static str,
+    target: ConnectorPath<'static>,
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+impl CompoundModel {
+    pub fn builder(system: SystemModel<'static>) -> CompoundModelBuilder {
+        CompoundModelBuilder {
+            system,
+            inputs: vec![],
+            outputs: vec![],
+        }
+    }
+
+    /// Returns the inner simulation, or `None` if the model wasn't
+    /// initialized yet.
+    pub fn simulation(&self) -> Option<&Simulation<'static>> {
+        self.simulation.as_ref()
+    }
+
+    /// Mutable variant of [CompoundModel::simulation].
+    pub fn simulation_mut(&mut self) -> Option<&mut Simulation<'static>> {
+        self.simulation.as_mut()
+    }
+
+    fn running(&mut self, ctx: &ModelCtx<'_>) -> Result<&mut Simulation<'static>, SimulationError> {
+        self.simulation
+            .as_mut()
+            .ok_or_else(|| SimulationError::Uninitialized {
+                id: ctx.model_id.to_string(),
+            })
+    }
+
+    /// Schedules an update for the time of the next inner entry.
+    fn schedule_next(&mut self, ctx: &ModelCtx<'_>) -> Result<(), SimulationError> {
+        ctx.cancel_updates();
+        if let Some(time) = self.running(ctx)?.next_time() {
+            ctx.schedule_update(TimeTrigger::Absolute(time))?;
+        }
+        Ok(())
+    }
+
+    fn forward_input(
+        &mut self,
+        index: usize,
+        event: ErasedEvent,
+        ctx: ModelCtx<'_>,
+    ) -> Result<(), SimulationError> {
+        let target = self.inputs[index].target.clone();
+        self.running(&ctx)?
+            .schedule_erased_event(ctx.time, event, target)?;
+        self.schedule_next(&ctx)
+    }
+}
+
+impl<'s> Model<'s> for CompoundModel {
+    fn input_connectors(&self) -> Vec<&'static str> {
+        self.inputs.iter().map(|it| it.name).collect()
+    }
+
+    fn output_connectors(&self) -> Vec<OutputConnectorInfo> {
+        self.outputs.clone()
+    }
+
+    fn get_input_handler<'h>(&self, index: usize) -> Option<Box<dyn ErasedInputHandler<'h, 's>>>
+    where
+        's: 'h,
+    {
+        let input = self.inputs.get(index)?;
+        Some(Box::new(ExternalInputHandler {
+            index,
+            type_id: input.type_id,
+            type_name: input.type_name,
+        }))
+    }
+
+    fn init(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        if let Some(system) = self.system.take() {
+            self.simulation = Some(Simulation::new_with_shared(
+                #[cfg(any(feature = "rand", feature = "mini_rng"))]
+                ctx.rng.clone(),
+                system,
+                ctx.time,
+                false,
+            )?);
+        }
+        self.schedule_next(&ctx)
+    }
+
+    fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        let simulation = self.running(&ctx)?;
+        while simulation.next_time().is_some_and(|it| it <= ctx.time) {
+            simulation.step_time()?;
+        }
+
+        let emitted = simulation
+            .inspect_model_mut::<ExposedOutputs>(OUTPUTS_ID)
+            .map(|it| std::mem::take(&mut it.emitted))
+            .unwrap_or_default();
+        for (index, event) in emitted {
+            let name = CowStr::Owned(self.outputs[index].id.clone());
+            ctx.push_erased_event(event, name, TimeTrigger::Now)?;
+        }
+
+        self.schedule_next(&ctx)
+    }
+
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        match &mut self.simulation {
+            Some(simulation) => simulation.reset(),
+            None => Ok(()),
+        }
+    }
+
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Delivers events received on an external input to the inner system.
+struct ExternalInputHandler {
+    index: usize,
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+impl<'h, 's: 'h> ErasedInputHandler<'h, 's> for ExternalInputHandler {
+    fn apply_event(
+        &self,
+        event: ErasedEvent,
+        ctx: ConnectorCtx<'s>,
+    ) -> Result<(), SimulationError> {
+        check_event_type(&event, self.type_id, self.type_name)?;
+        let ConnectorCtx {
+            model_ctx,
+            mut on_model,
+        } = ctx;
+        let model = unsafe { on_model.cast_mut::<CompoundModel>() }.ok_or(
+            RoutingError::InvalidModelType {
+                expected: std::any::type_name::<CompoundModel>(),
+            },
+        )?;
+        model.forward_input(self.index, event, model_ctx)
+    }
+
+    fn model_type_id(&self) -> TypeId {
+        TypeId::of::<CompoundModel>()
+    }
+
+    fn event_type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    fn event_type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+/// Inner model collecting events sent through exposed outputs until the
+/// compound model forwards them.
+struct ExposedOutputs {
+    names: Vec<&'static str>,
+    types: Vec<(TypeId, &'static str)>,
+    emitted: Vec<(usize, ErasedEvent)>,
+}
+
+impl<'s> Model<'s> for ExposedOutputs {
+    fn input_connectors(&self) -> Vec<&'static str> {
+        self.names.clone()
+    }
+
+    fn output_connectors(&self) -> Vec<OutputConnectorInfo> {
+        vec![]
+    }
+
+    fn get_input_handler<'h>(&self, index: usize) -> Option<Box<dyn ErasedInputHandler<'h, 's>>>
+    where
+        's: 'h,
+    {
+        let (type_id, type_name) = *self.types.get(index)?;
+        Some(Box::new(ExposedOutputHandler {
+            index,
+            type_id,
+            type_name,
+        }))
+    }
+
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.emitted.clear();
+        Ok(())
+    }
+
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<Self>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+struct ExposedOutputHandler {
+    index: usize,
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+impl<'h, 's: 'h> ErasedInputHandler<'h, 's> for ExposedOutputHandler {
+    fn apply_event(
+        &self,
+        event: ErasedEvent,
+        ctx: ConnectorCtx<'s>,
+    ) -> Result<(), SimulationError> {
+        check_event_type(&event, self.type_id, self.type_name)?;
+        let ConnectorCtx { mut on_model, .. } = ctx;
+        let model = unsafe { on_model.cast_mut::<ExposedOutputs>() }.ok_or(
+            RoutingError::InvalidModelType {
+                expected: std::any::type_name::<ExposedOutputs>(),
+            },
+        )?;
+        model.emitted.push((self.index, event));
+        Ok(())
+    }
+
+    fn model_type_id(&self) -> TypeId {
+        TypeId::of::<ExposedOutputs>()
+    }
+
+    fn event_type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    fn event_type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+fn check_event_type(
+    event: &ErasedEvent,
+    type_id: TypeId,
+    type_name: &'static str,
+) -> Result<(), RoutingError> {
+    if event.type_id != type_id {
+        return Err(RoutingError::InvalidEventType {
+            event_type: event.type_name(),
+            expected: type_name,
+        });
+    }
+    Ok(())
+}
+
+/// Maps connectors of an inner system to connectors of a [CompoundModel].
+pub struct CompoundModelBuilder {
+    system: SystemModel<'static>,
+    inputs: Vec<(&'static str, ConnectorPath<'static>)>,
+    outputs: Vec<(&'static str, ConnectorPath<'static>)>,
+}
+
+impl CompoundModelBuilder {
+    /// Exposes `target` input connector of an inner model as input connector
+    /// named `name`.
+    pub fn input(mut self, name: &'static str, target: ConnectorPath<'static>) -> Self {
+        self.inputs.push((name, target));
+        self
+    }
+
+    /// Exposes `source` output connector of an inner model as output
+    /// connector named `name`.
+    ///
+    /// Exposed output connectors can't also be connected to inner models.
+    pub fn output(mut self, name: &'static str, source: ConnectorPath<'static>) -> Self {
+        self.outputs.push((name, source));
+        self
+    }
+
+    /// Validates the inner system along with exposed connectors.
+    ///
+    /// Names of exposed inputs and of exposed outputs must be unique.
+    pub fn build(self) -> Result<CompoundModel, ValidationError> {
+        let CompoundModelBuilder {
+            mut system,
+            inputs,
+            outputs,
+        } = self;
+
+        for exposed in [&inputs, &outputs] {
+            for (i, (name, _)) in exposed.iter().enumerate() {
+                if exposed[..i].iter().any(|(other, _)| other == name) {
+                    return Err(ValidationError::RepeatedExposedConnector {
+                        connector: name.to_string(),
+                    });
+                }
+            }
+        }
+
+        let inputs = inputs
+            .into_iter()
+            .map(|(name, target)| {
+                let model = inner_model(&system, &target)?;
+                let handler = model
+                    .get_input_handler_by_name(target.connector.as_ref())
+                    .ok_or_else(|| ValidationError::MissingConnector {
+                        model: target.model.to_string(),
+                        id: target.connector.to_string(),
+                        suggestion: closest_match(&target.connector, model.input_connectors()),
+                    })?;
+                Ok(ExternalInput {
+                    name,
+                    type_id: handler.event_type_id(),
+                    type_name: handler.event_type_name(),
+                    target,
+                })
+            })
+            .collect::<Result<Vec<_>, ValidationError>>()?;
+
+        let mut exposed = ExposedOutputs {
+            names: vec![],
+            types: vec![],
+            emitted: vec![],
+        };
+        let mut output_infos = Vec::with_capacity(outputs.len());
+        for (name, source) in &outputs {
+            let model = inner_model(&system, source)?;
+            let connectors = model.output_connectors();
+            let info = connectors
+                .iter()
+                .find(|it| it.id() == source.connector.as_ref())
+                .ok_or_else(|| ValidationError::MissingConnector {
+                    model: source.model.to_string(),
+                    id: source.connector.to_string(),
+                    suggestion: closest_match(
+                        &source.connector,
+                        connectors.iter().map(|it| it.id()),
+                    ),
+                })?;
+            if system.routes.contains_key(source) {
+                return Err(ValidationError::RepeatedOutput {
+                    connector: source.connector.to_string(),
+                });
+            }
+
+            exposed.names.push(name);
+            exposed.types.push((info.type_id(), info.type_name()));
+            output_infos.push(OutputConnectorInfo {
+                id: name.to_string(),
+                type_id: info.type_id(),
+                type_name: info.type_name(),
+                description: info.description.clone(),
+                signal: info.is_signal(),
+            });
+        }
+
+        system.push_model(OUTPUTS_ID, exposed);
+        for (name, source) in outputs {
+            system.push_route(source, ConnectorPath::new_const(OUTPUTS_ID, name));
+        }
+        system.validate()?;
+
+        Ok(CompoundModel {
+            system: Some(system),
+            simulation: None,
+            inputs,
+            outputs: output_infos,
+        })
+    }
+}
+
+fn inner_model<'a>(
+    system: &'a SystemModel<'static>,
+    path: &ConnectorPath<'static>,
+) -> Result<&'a dyn Model<'static>, ValidationError> {
+    system
+        .models
+        .get(&path.model)
+        .ok_or_else(|| ValidationError::MissingModel {
+            id: path.model.to_string(),
+            suggestion: closest_match(&path.model, system.models.ids()),
+        })
+}
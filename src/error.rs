@@ -2,23 +2,50 @@ use thiserror::Error;
 
 use crate::prelude::Time;
 
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(it) => format!("; did you mean '{}'?", it),
+        None => String::new(),
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ValidationError {
-    #[error("Simulation missing a model with id: {id}")]
-    MissingModel { id: String },
-    #[error("A model '{model}' is missing connector: {id}")]
-    MissingConnector { model: String, id: String },
-    #[error("Connection output ({output_model}::{output_connector}) and input ({input_model}::{input_connector}) types do not match")]
+    #[error("Simulation missing a model with id: {id}{}", did_you_mean(suggestion))]
+    MissingModel {
+        id: String,
+        /// Closest existing model id, if any is similar enough.
+        suggestion: Option<String>,
+    },
+    #[error(
+        "A model '{model}' is missing connector: {id}{}",
+        did_you_mean(suggestion)
+    )]
+    MissingConnector {
+        model: String,
+        id: String,
+        /// Closest existing connector name, if any is similar enough.
+        suggestion: Option<String>,
+    },
+    #[error("Connection output ({output_model}::{output_connector}: {output_type}) and input ({input_model}::{input_connector}: {input_type}) types do not match")]
     ConnectionTypeMismatch {
         output_model: String,
         output_connector: String,
+        output_type: &'static str,
         input_model: String,
         input_connector: String,
+        input_type: &'static str,
     },
     #[error("Connector '{connector}' takes in a wrong model type")]
     InvalidConnectorModel { connector: &'static str },
     #[error("Output connector '{connector}' connects to multiple inputs")]
     RepeatedOutput { connector: String },
+    #[error("Compound model exposes connector '{connector}' more than once")]
+    RepeatedExposedConnector { connector: String },
+    #[error("Connectors of model '{model}' changed after validation")]
+    ConnectorsChanged { model: String },
+    #[error("Routes form a cycle: {}", models.join(" -> "))]
+    CycleDetected { models: Vec<String> },
     #[error("Model store error: {0}")]
     ModelStore(
         #[from]
@@ -31,6 +58,8 @@ pub enum ValidationError {
 pub enum SchedulerError {
     #[error("Tried scheduling an occurence in the past: {insertion}; current time is: {current}")]
     TimeRegression { current: Time, insertion: Time },
+    #[error("Tried scheduling an occurence at invalid time: {0}")]
+    InvalidTime(Time),
 }
 
 #[derive(Debug, Error)]
@@ -48,6 +77,8 @@ pub enum RoutingError {
     UnknownModelConnector { model: String, connector: String },
     #[error("Event generated by {model} is missing a target")]
     MissingEventTarget { model: String },
+    #[error("Tried delivering an event to '{model}::{connector}' while the model is handling another one")]
+    ReentrantDelivery { model: String, connector: String },
 }
 
 #[derive(Debug, Error)]
@@ -56,12 +87,54 @@ pub enum ModelStoreError {
     ModelMissing,
     #[error("Tried returning a model into an occupied slot")]
     SlotOccupied,
+    #[error("Tried borrowing model '{id}' more than once")]
+    AliasedBorrow { id: String },
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("Unknown model type: {name}{}", did_you_mean(suggestion))]
+    UnknownType {
+        name: String,
+        /// Closest registered type name, if any is similar enough.
+        suggestion: Option<String>,
+    },
+    #[error("Invalid configuration of model type '{name}': {source}")]
+    InvalidConfig {
+        name: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug, Error)]
+pub enum StateError {
+    #[error("Model doesn't support state snapshots")]
+    Unsupported,
+    #[error("Invalid model state: {0}")]
+    Invalid(
+        #[from]
+        #[source]
+        serde_json::Error,
+    ),
 }
 
 #[derive(Debug, Error)]
 pub enum SimulationError {
     #[error("Unable to locate model: {id}")]
     ModelNotFound { id: String },
+    #[error("Simulation didn't finish within {limit} steps")]
+    StepLimitExceeded { limit: usize },
+    #[error("Livelock detected: more than {limit} entries processed at {time}; last one targeted model '{model}'")]
+    Livelock {
+        model: String,
+        time: Time,
+        limit: usize,
+    },
+    #[error("Realtime scale {scale} isn't a finite positive number")]
+    InvalidRealtimeScale { scale: f64 },
 
     #[error("Scheduler error: {0}")]
     Scheduler(
@@ -87,6 +160,21 @@ pub enum SimulationError {
         #[source]
         ModelStoreError,
     ),
+    #[error("Model '{id}' was used before it was initialized")]
+    Uninitialized { id: String },
+    #[error("Initialization of model '{id}' failed: {source}")]
+    InitFailed {
+        id: String,
+        #[source]
+        source: Box<SimulationError>,
+    },
+    #[cfg(feature = "serde")]
+    #[error("Unable to restore state of model '{model}': {source}")]
+    State {
+        model: String,
+        #[source]
+        source: StateError,
+    },
     #[error(transparent)]
     Other(Box<dyn std::error::Error>),
 }
@@ -1,11 +1,22 @@
-use std::any::{Any, TypeId};
+use std::{
+    any::{Any, TypeId},
+    sync::Arc,
+};
 
+/// Payload carried by an [Event].
+///
+/// Messages must be `'static`, so they can't borrow from models. Large data
+/// shared between models can be sent as [Arc] handles instead (see
+/// [Event::shared]), which makes sending and fanning out events cheap as only
+/// the handle is cloned.
 pub trait Message: Any + 'static {}
 impl<T> Message for T where T: Any + 'static {}
 
 pub struct Event<M: Message> {
     type_info: TypeId,
     pub data: Box<M>,
+    /// Present if the event was created with [Event::new_cloneable].
+    clone_fn: Option<CloneFn>,
 }
 
 impl<M: Message> Event<M> {
@@ -13,22 +24,38 @@ impl<M: Message> Event<M> {
         Event {
             type_info: TypeId::of::<M>(),
             data: Box::new(data),
+            clone_fn: None,
         }
     }
 
-    /// Erasing event type information makes it unsafe to drop the event until
-    /// Message type has been restored to original value. Doing so will cause a
-    /// memory leak.
-    pub(crate) unsafe fn erase_message_type(self) -> ErasedEvent {
-        let data: *const Box<M> = &self.data;
-
+    /// Erases event type information.
+    ///
+    /// Erased event keeps a drop function for the original message type, so
+    /// it can be dropped without restoring the type first.
+    pub(crate) fn erase_message_type(self) -> ErasedEvent {
         ErasedEvent {
             type_id: self.type_info,
             type_name: std::any::type_name::<M>(),
-            data: data as *const Box<ErasedMessage>,
+            data: Box::into_raw(self.data) as *mut ErasedMessage,
+            drop_fn: drop_message::<M>,
+            clone_fn: self.clone_fn,
         }
     }
 
+    /// Returns a copy of the event, or `None` if it wasn't created with
+    /// [Event::new_cloneable].
+    ///
+    /// Events with non-Clone payloads can't be fanned out or replayed.
+    pub fn try_clone(&self) -> Option<Event<M>> {
+        let clone_fn = self.clone_fn?;
+        let data = unsafe { clone_fn(&*self.data as *const M as *const ErasedMessage) };
+        Some(Event {
+            type_info: self.type_info,
+            data: unsafe { Box::from_raw(data as *mut M) },
+            clone_fn: self.clone_fn,
+        })
+    }
+
     pub fn inner(&self) -> &M {
         &*self.data
     }
@@ -36,6 +63,35 @@ impl<M: Message> Event<M> {
     pub fn into_inner(self) -> M {
         *self.data
     }
+
+    /// Converts event payload into a different message type.
+    ///
+    /// Returned event isn't cloneable.
+    pub fn map<B: Message>(self, f: impl FnOnce(M) -> B) -> Event<B> {
+        Event::new(f(self.into_inner()))
+    }
+}
+
+impl<M: Message + Clone> Event<M> {
+    /// Creates an event that can be cloned with [Event::try_clone], also
+    /// after its type was erased.
+    pub fn new_cloneable(data: M) -> Self {
+        Event {
+            type_info: TypeId::of::<M>(),
+            data: Box::new(data),
+            clone_fn: Some(clone_message::<M>),
+        }
+    }
+}
+
+impl<M: Message> Event<Arc<M>> {
+    /// Creates an event carrying a shared handle to `data`.
+    ///
+    /// Input handlers receive it as `Arc<M>`. Such events can always be
+    /// cloned, even if `M` doesn't implement [Clone].
+    pub fn shared(data: Arc<M>) -> Self {
+        Event::new_cloneable(data)
+    }
 }
 
 pub type Signal = Event<()>;
@@ -46,31 +102,94 @@ pub fn Signal() -> Signal {
 }
 
 struct ErasedMessage;
+
+/// Drops message of type `M` behind an erased pointer.
+///
+/// # Safety
+///
+/// `data` must be created by [Box::into_raw] from a `Box<M>`.
+unsafe fn drop_message<M: Message>(data: *mut ErasedMessage) {
+    drop(Box::from_raw(data as *mut M));
+}
+
+/// Clones message of type `M` behind an erased pointer into a new allocation.
+///
+/// # Safety
+///
+/// `data` must point to a valid `M`.
+unsafe fn clone_message<M: Message + Clone>(data: *const ErasedMessage) -> *mut ErasedMessage {
+    Box::into_raw(Box::new((*(data as *const M)).clone())) as *mut ErasedMessage
+}
+
+type CloneFn = unsafe fn(*const ErasedMessage) -> *mut ErasedMessage;
+
 pub struct ErasedEvent {
     pub(crate) type_id: TypeId,
     pub(crate) type_name: &'static str,
-    data: *const Box<ErasedMessage>,
+    data: *mut ErasedMessage,
+    drop_fn: unsafe fn(*mut ErasedMessage),
+    /// Present if the event was created with [Event::new_cloneable].
+    clone_fn: Option<CloneFn>,
 }
 
 impl ErasedEvent {
+    /// Returns a reference to the message if it's of type `M`, without
+    /// consuming the event.
+    ///
+    /// Returned reference borrows the event, so the event can't be dropped or
+    /// restored while the reference is alive.
+    pub fn peek<M: Message>(&self) -> Option<&M> {
+        if self.type_id == TypeId::of::<M>() {
+            Some(unsafe { &*(self.data as *const M) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a copy of the event, or `None` if the original event wasn't
+    /// created with [Event::new_cloneable].
+    pub fn try_clone(&self) -> Option<ErasedEvent> {
+        let clone_fn = self.clone_fn?;
+        Some(ErasedEvent {
+            type_id: self.type_id,
+            type_name: self.type_name,
+            data: unsafe { clone_fn(self.data) },
+            drop_fn: self.drop_fn,
+            clone_fn: self.clone_fn,
+        })
+    }
+
+    /// Returns the name of the message type.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
     pub fn try_restore_type<M: Message>(self) -> Result<Event<M>, ErasedEvent> {
-        let data = self.data as *const Box<M>;
-        unsafe {
-            if self.type_id == TypeId::of::<M>() {
-                Ok(Event {
-                    type_info: self.type_id,
-                    data: std::ptr::read(data),
-                })
-            } else {
-                Err(self)
-            }
+        if self.type_id == TypeId::of::<M>() {
+            let data = unsafe { Box::from_raw(self.data as *mut M) };
+            let clone_fn = self.clone_fn;
+            // ownership of data was moved into the restored event
+            std::mem::forget(self);
+            Ok(Event {
+                type_info: TypeId::of::<M>(),
+                data,
+                clone_fn,
+            })
+        } else {
+            Err(self)
         }
     }
 }
 
+impl Drop for ErasedEvent {
+    fn drop(&mut self) {
+        unsafe { (self.drop_fn)(self.data) }
+    }
+}
+
 impl<M: Message> From<Event<M>> for ErasedEvent {
     fn from(value: Event<M>) -> Self {
-        unsafe { value.erase_message_type() }
+        value.erase_message_type()
     }
 }
 
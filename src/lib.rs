@@ -1,29 +1,51 @@
 #![allow(incomplete_features)]
 #![feature(const_type_id, box_into_inner)]
 
+pub mod compound;
 pub mod error;
 pub mod event;
+pub mod metrics;
 pub mod model;
+#[cfg(feature = "serde")]
+pub mod registry;
 pub mod routes;
 pub mod simulation;
+#[cfg(feature = "stats")]
+pub mod stats;
 pub mod system;
 pub mod time;
+pub mod trace;
 
 pub(crate) mod util;
 
+#[doc(hidden)]
+#[cfg(feature = "serde")]
+pub use serde_json;
+
 pub mod prelude {
+    pub use crate::compound::*;
     pub use crate::event::*;
+    pub use crate::metrics::*;
     pub use crate::model::*;
+    #[cfg(feature = "serde")]
+    pub use crate::registry::*;
     pub use crate::routes::*;
     pub use crate::simulation::*;
+    #[cfg(feature = "stats")]
+    pub use crate::stats::*;
     pub use crate::system::*;
 
     pub use crate::time::TimeTrigger::Now;
     pub use crate::time::*;
+    pub use crate::trace::*;
 
     pub use crate::error::*;
     pub use crate::util::const_type_id;
-    #[cfg(feature = "rand")]
+    #[cfg(feature = "mini_rng")]
+    pub use crate::util::MiniRng;
+    #[cfg(all(feature = "mini_rng", not(feature = "rand")))]
+    pub use crate::util::MiniSample;
+    #[cfg(any(feature = "rand", feature = "mini_rng"))]
     pub use crate::util::SimulationRng;
 
     // macros
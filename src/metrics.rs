@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use crate::{routes::Route, util::CowStr};
+
+/// Event counts collected by a simulation with enabled metrics.
+#[derive(Default)]
+pub struct SimulationMetrics<'s> {
+    route_events: HashMap<Route<'s>, usize>,
+    model_updates: HashMap<CowStr<'s>, usize>,
+}
+
+impl<'s> SimulationMetrics<'s> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record_event(&mut self, route: &Route<'s>) {
+        match self.route_events.get_mut(route) {
+            Some(count) => *count += 1,
+            None => {
+                self.route_events.insert(route.clone(), 1);
+            }
+        }
+    }
+
+    pub(crate) fn record_update(&mut self, model: &CowStr<'s>) {
+        match self.model_updates.get_mut(model) {
+            Some(count) => *count += 1,
+            None => {
+                self.model_updates.insert(model.clone(), 1);
+            }
+        }
+    }
+
+    /// Returns number of events that were delivered through a route.
+    pub fn route_events(&self, route: &Route<'s>) -> usize {
+        self.route_events.get(route).copied().unwrap_or_default()
+    }
+
+    /// Returns number of internal updates handled by a model.
+    pub fn model_updates(&self, model: impl AsRef<str>) -> usize {
+        self.model_updates
+            .get(model.as_ref())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn iter_route_events(&self) -> impl Iterator<Item = (&Route<'s>, usize)> {
+        self.route_events
+            .iter()
+            .map(|(route, count)| (route, *count))
+    }
+
+    pub fn iter_model_updates(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.model_updates
+            .iter()
+            .map(|(model, count)| (model.as_ref(), *count))
+    }
+}
@@ -1,9 +1,11 @@
-use std::any::TypeId;
+use std::any::{Any, TypeId};
 
+#[cfg(feature = "serde")]
+use crate::error::StateError;
 use crate::{
     error::{RoutingError, SimulationError},
     event::{ErasedEvent, Event, Message},
-    routes::OutputConnectorInfo,
+    routes::{InputConnectorInfo, OutputConnectorInfo},
     simulation::{ConnectorCtx, ModelCtx},
 };
 
@@ -25,6 +27,7 @@ pub trait ErasedInputHandler<'h, 's: 'h>: 'h {
         -> Result<(), SimulationError>;
     fn model_type_id(&self) -> TypeId;
     fn event_type_id(&self) -> TypeId;
+    fn event_type_name(&self) -> &'static str;
 }
 
 impl<'h, 's: 'h, C: InputHandler<'s> + 'h> ErasedInputHandler<'h, 's> for C {
@@ -63,6 +66,10 @@ impl<'h, 's: 'h, C: InputHandler<'s> + 'h> ErasedInputHandler<'h, 's> for C {
     fn event_type_id(&self) -> TypeId {
         TypeId::of::<C::In>()
     }
+
+    fn event_type_name(&self) -> &'static str {
+        std::any::type_name::<C::In>()
+    }
 }
 
 pub trait Model<'s> {
@@ -71,6 +78,27 @@ pub trait Model<'s> {
     /// Returned value must stay the same for each model instance for the
     /// duration of the simulation.
     fn input_connectors(&self) -> Vec<&'static str>;
+    /// Lists all model input connectors along with their event types.
+    ///
+    /// Default implementation derives them from [Self::input_connectors] and
+    /// input handlers, treating connectors that receive `()` as signals.
+    fn input_connector_infos(&self) -> Vec<InputConnectorInfo> {
+        self.input_connectors()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, id)| {
+                let handler = self.get_input_handler(i)?;
+                let type_id = handler.event_type_id();
+                Some(InputConnectorInfo {
+                    id,
+                    type_id,
+                    type_name: handler.event_type_name(),
+                    signal: type_id == TypeId::of::<()>(),
+                    priority: 0,
+                })
+            })
+            .collect()
+    }
     /// Lists all model output connectors
     ///
     /// Returned value must stay the same for each model instance for the
@@ -84,6 +112,15 @@ pub trait Model<'s> {
     where
         's: 'h;
 
+    /// Returns the type id of the model handled by input handler at `index`
+    /// without constructing the handler.
+    ///
+    /// Default implementation constructs the handler to query it.
+    fn input_model_type_id(&self, index: usize) -> Option<TypeId> {
+        self.get_input_handler(index)
+            .map(|handler| handler.model_type_id())
+    }
+
     /// Called during initalization.
     ///
     /// This method allows models like generators to schedule their inital changes.
@@ -99,7 +136,62 @@ pub trait Model<'s> {
         Ok(())
     }
 
+    /// Called by [Simulation::reset](crate::simulation::Simulation::reset)
+    /// before [Self::init].
+    ///
+    /// Models must restore their initial state here if they're meant to be
+    /// reused for multiple runs.
+    #[allow(unused_variables)]
+    fn reset(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        Ok(())
+    }
+
+    /// Returns serialized model state.
+    ///
+    /// Returns [StateError::Unsupported] by default;
+    /// `#[litesim_model(state)]` implements it through [ModelState].
+    #[cfg(feature = "serde")]
+    fn state_snapshot(&self) -> Result<serde_json::Value, StateError> {
+        Err(StateError::Unsupported)
+    }
+
+    /// Replaces model state with a previously taken
+    /// [snapshot](Self::state_snapshot).
+    #[allow(unused_variables)]
+    #[cfg(feature = "serde")]
+    fn restore_state_snapshot(&mut self, state: serde_json::Value) -> Result<(), StateError> {
+        Err(StateError::Unsupported)
+    }
+
     fn type_id(&self) -> TypeId;
+
+    /// Returns the model as [Any], allowing it to be downcast to its concrete
+    /// type.
+    fn as_any(&self) -> &dyn Any;
+    /// Mutable variant of [Model::as_any].
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// Models with state that can be saved and restored.
+///
+/// Implemented for all models that implement [Serialize](serde::Serialize)
+/// and [Deserialize](serde::Deserialize).
+#[cfg(feature = "serde")]
+pub trait ModelState<'s>: Model<'s> {
+    fn state_json(&self) -> Result<serde_json::Value, StateError>;
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), StateError>;
+}
+
+#[cfg(feature = "serde")]
+impl<'s, M: Model<'s> + serde::Serialize + serde::de::DeserializeOwned> ModelState<'s> for M {
+    fn state_json(&self) -> Result<serde_json::Value, StateError> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), StateError> {
+        *self = serde_json::from_value(state)?;
+        Ok(())
+    }
 }
 
 pub trait ModelImpl<'s>: Model<'s> {
@@ -121,15 +213,17 @@ pub trait ModelImpl<'s>: Model<'s> {
     }
 
     fn input_type_id(&self, name: impl AsRef<str>) -> Option<TypeId> {
-        let handler = self.get_input_handler_by_name(name)?;
-        Some(handler.event_type_id())
+        self.input_connector_infos()
+            .iter()
+            .find(|it| it.id == name.as_ref())
+            .map(|it| it.type_id)
     }
 
     fn output_type_id(&self, name: impl AsRef<str>) -> Option<TypeId> {
         self.output_connectors()
             .iter()
-            .find(|it| it.0 == name.as_ref())
-            .map(|it| it.1.clone())
+            .find(|it| it.id == name.as_ref())
+            .map(|it| it.type_id)
     }
 }
 
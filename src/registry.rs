@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+
+use crate::{error::RegistryError, model::Model, system::SystemModel, util::closest_match};
+
+/// Models that can be constructed from a JSON configuration.
+///
+/// Implemented for all models that implement
+/// [Deserialize](serde::Deserialize).
+pub trait DeserializeModel<'s>: Model<'s> + Sized + 'static {
+    fn from_config(config: serde_json::Value) -> Result<Self, serde_json::Error>;
+}
+
+impl<'s, M: Model<'s> + DeserializeOwned + 'static> DeserializeModel<'s> for M {
+    fn from_config(config: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(config)
+    }
+}
+
+type Constructor<'s> =
+    Box<dyn Fn(serde_json::Value) -> Result<Box<dyn Model<'s>>, serde_json::Error>>;
+
+/// Maps model type names to constructors, allowing systems to be built from
+/// configuration files.
+#[derive(Default)]
+pub struct ModelRegistry<'s> {
+    constructors: HashMap<String, Constructor<'s>>,
+}
+
+impl<'s> ModelRegistry<'s> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers model type `M` under provided name, replacing any type
+    /// previously registered under it.
+    pub fn register<M: DeserializeModel<'s>>(&mut self, name: impl ToString) {
+        self.register_with(name, |config| {
+            Ok(Box::new(M::from_config(config)?) as Box<dyn Model<'s>>)
+        });
+    }
+
+    /// Registers a custom constructor under provided name.
+    pub fn register_with(
+        &mut self,
+        name: impl ToString,
+        constructor: impl Fn(serde_json::Value) -> Result<Box<dyn Model<'s>>, serde_json::Error>
+            + 'static,
+    ) {
+        self.constructors
+            .insert(name.to_string(), Box::new(constructor));
+    }
+
+    pub fn contains(&self, name: impl AsRef<str>) -> bool {
+        self.constructors.contains_key(name.as_ref())
+    }
+
+    /// Returns names of all registered model types.
+    pub fn names(&self) -> impl Iterator<Item = &str> + use<'_, 's> {
+        self.constructors.keys().map(String::as_str)
+    }
+
+    /// Constructs a model of type registered under `name` from `config`.
+    pub fn construct(
+        &self,
+        name: impl AsRef<str>,
+        config: serde_json::Value,
+    ) -> Result<Box<dyn Model<'s>>, RegistryError> {
+        let name = name.as_ref();
+        let constructor =
+            self.constructors
+                .get(name)
+                .ok_or_else(|| RegistryError::UnknownType {
+                    name: name.to_string(),
+                    suggestion: closest_match(name, self.names()),
+                })?;
+        constructor(config).map_err(|source| RegistryError::InvalidConfig {
+            name: name.to_string(),
+            source,
+        })
+    }
+
+    /// Constructs a model like [ModelRegistry::construct] and adds it to
+    /// `system` with provided id.
+    pub fn push_model(
+        &self,
+        system: &mut SystemModel<'s>,
+        id: impl ToString,
+        name: impl AsRef<str>,
+        config: serde_json::Value,
+    ) -> Result<(), RegistryError> {
+        let model = self.construct(name, config)?;
+        system.push_boxed_model(id, model);
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 use std::{any::TypeId, fmt::Debug};
 
-use crate::util::CowStr;
+use crate::{
+    event::{ErasedEvent, Message},
+    util::CowStr,
+};
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -16,6 +19,15 @@ impl<'s> ConnectorPath<'s> {
             connector: CowStr::Owned(connector.as_ref().to_string()),
         }
     }
+
+    /// Creates a path borrowing provided names, which allows using it in
+    /// constants.
+    pub const fn new_const(model: &'s str, connector: &'s str) -> Self {
+        Self {
+            model: CowStr::Borrowed(model),
+            connector: CowStr::Borrowed(connector),
+        }
+    }
 }
 
 impl Debug for ConnectorPath<'_> {
@@ -26,22 +38,31 @@ impl Debug for ConnectorPath<'_> {
 
 #[macro_export]
 macro_rules! connection {
+    ($model:literal :: $connector:tt) => {
+        ::litesim::routes::ConnectorPath {
+            model: std::borrow::Cow::Borrowed(concat!($model)),
+            connector: std::borrow::Cow::Borrowed(stringify!($connector)),
+        }
+    };
     ($model:tt :: $connector:tt) => {
         ::litesim::routes::ConnectorPath {
             model: std::borrow::Cow::Borrowed(stringify!($model)),
             connector: std::borrow::Cow::Borrowed(stringify!($connector)),
         }
     };
+    ($model:expr, $connector:expr) => {
+        ::litesim::routes::ConnectorPath::new_const($model, $connector)
+    };
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EventSource<'s> {
     External,
     Internal,
     Model(ConnectorPath<'s>),
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Route<'s> {
     pub from: EventSource<'s>,
     pub to: ConnectorPath<'s>,
@@ -114,10 +135,161 @@ impl<'s> From<(&ConnectorPath<'s>, &ConnectorPath<'s>)> for Route<'s> {
     }
 }
 
-pub struct OutputConnectorInfo(pub(crate) String, pub(crate) TypeId);
+type MapFn = dyn Fn(ErasedEvent) -> ErasedEvent;
+
+/// Conversion applied to events passing through a route.
+///
+/// See [SystemModel::push_route_mapped](crate::system::SystemModel::push_route_mapped).
+pub struct RouteMap {
+    pub(crate) input: TypeId,
+    pub(crate) output: TypeId,
+    input_name: &'static str,
+    output_name: &'static str,
+    map: Box<MapFn>,
+}
+
+impl RouteMap {
+    pub fn new<A: Message, B: Message>(map: impl Fn(A) -> B + 'static) -> Self {
+        RouteMap {
+            input: TypeId::of::<A>(),
+            output: TypeId::of::<B>(),
+            input_name: std::any::type_name::<A>(),
+            output_name: std::any::type_name::<B>(),
+            map: Box::new(move |event| match event.try_restore_type::<A>() {
+                Ok(event) => event.map(&map).into(),
+                // validation ensures types match; mismatched events are passed
+                // through so the input handler reports them
+                Err(event) => event,
+            }),
+        }
+    }
+
+    pub fn input_type_id(&self) -> TypeId {
+        self.input
+    }
+
+    pub fn output_type_id(&self) -> TypeId {
+        self.output
+    }
+
+    pub fn input_type_name(&self) -> &'static str {
+        self.input_name
+    }
+
+    pub fn output_type_name(&self) -> &'static str {
+        self.output_name
+    }
+
+    pub fn apply(&self, event: ErasedEvent) -> ErasedEvent {
+        (self.map)(event)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InputConnectorInfo {
+    pub(crate) id: &'static str,
+    pub(crate) type_id: TypeId,
+    pub(crate) type_name: &'static str,
+    pub(crate) signal: bool,
+    pub(crate) priority: i32,
+}
+
+impl InputConnectorInfo {
+    pub fn new<T: 'static>(id: &'static str) -> Self {
+        InputConnectorInfo {
+            id,
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            signal: false,
+            priority: 0,
+        }
+    }
+
+    /// Marks the connector as a signal connector.
+    pub fn with_signal(mut self, signal: bool) -> Self {
+        self.signal = signal;
+        self
+    }
+
+    /// Sets the priority of the connector.
+    ///
+    /// Events delivered to the same model at the same time are applied in
+    /// order of descending priority of their connectors. Default is `0`.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub fn is_signal(&self) -> bool {
+        self.signal
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OutputConnectorInfo {
+    pub(crate) id: String,
+    pub(crate) type_id: TypeId,
+    pub(crate) type_name: &'static str,
+    pub(crate) description: Option<String>,
+    pub(crate) signal: bool,
+}
 
 impl OutputConnectorInfo {
     pub fn new<T: 'static>(id: impl ToString) -> Self {
-        OutputConnectorInfo(id.to_string(), TypeId::of::<T>())
+        OutputConnectorInfo {
+            id: id.to_string(),
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            description: None,
+            signal: false,
+        }
+    }
+
+    /// Sets a human readable description of the connector.
+    pub fn with_description(mut self, description: impl ToString) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Marks the connector as a signal connector.
+    pub fn with_signal(mut self, signal: bool) -> Self {
+        self.signal = signal;
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn is_signal(&self) -> bool {
+        self.signal
     }
 }
@@ -1,73 +1,229 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     pin::Pin,
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
 };
 
 #[cfg(feature = "rand")]
-mod rand_imports {
-    pub use std::cell::RefCell;
-    pub use std::rc::Rc;
+use rand::Rng;
 
-    pub use rand::Rng;
-
-    pub use crate::util::SimulationRng;
-}
-#[cfg(feature = "rand")]
-use rand_imports::*;
+#[cfg(feature = "serde")]
+use crate::error::StateError;
+#[cfg(all(feature = "mini_rng", not(feature = "rand")))]
+use crate::util::MiniSample;
+#[cfg(any(feature = "rand", feature = "mini_rng"))]
+use crate::util::SimulationRng;
 
 use crate::{
-    error::{RoutingError, SchedulerError, SimulationError},
-    event::{Event, Message},
-    model::ModelImpl,
+    error::{ModelStoreError, RoutingError, SchedulerError, SimulationError},
+    event::{Event, Message, Signal},
+    metrics::SimulationMetrics,
+    model::{Model, ModelImpl},
     prelude::{BorrowedModel, ErasedEvent, TimeBounds},
     routes::{ConnectorPath, EventSource, Route},
     system::{AdjacentModels, SystemModel},
-    time::{Time, TimeTrigger},
+    time::{Time, TimeDelta, TimeTrigger},
+    trace::SimulationObserver,
     util::{CowStr, ToCowStr},
 };
 
+/// Determines what happens with events whose target model or connector
+/// doesn't exist.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLetterPolicy {
+    /// Return a routing error from [Simulation::step].
+    #[default]
+    Abort,
+    /// Store undelivered events so they can be accessed through
+    /// [Simulation::dead_letters].
+    Collect,
+    /// Silently drop undelivered events.
+    Drop,
+}
+
+/// Determines what happens when handling a scheduled entry fails.
+#[derive(Default)]
+pub enum ErrorPolicy {
+    /// Return the error from [Simulation::step].
+    #[default]
+    Abort,
+    /// Pass the error to provided callback and continue the simulation.
+    Continue(Box<dyn FnMut(&SimulationError)>),
+}
+
+/// Discrete event simulation of a [SystemModel].
+///
+/// Simulations are single-threaded: models, scheduler and RNG are shared
+/// through `Rc<RefCell<_>>` and raw pointers, so `Simulation` is neither
+/// [Send] nor [Sync]. To run one on a worker thread, construct it on that
+/// thread and communicate with it through channels (see
+/// [Simulation::run_interactive]).
 #[allow(dead_code)]
 pub struct Simulation<'s> {
-    #[cfg(feature = "rand")]
+    #[cfg(any(feature = "rand", feature = "mini_rng"))]
     global_rng: Rc<RefCell<dyn SimulationRng>>,
     system: Pin<Box<SystemModel<'s>>>,
     initial_time: Time,
-    scheduler: Pin<Box<Scheduler<'s>>>,
+    /// Order in which models are initialized.
+    init_order: Vec<CowStr<'s>>,
+    /// Models that deferred their initialization until their first input.
+    deferred_init: Rc<RefCell<HashSet<CowStr<'s>>>>,
+    scheduler: Rc<RefCell<Scheduler<'s>>>,
+    metrics: Option<SimulationMetrics<'s>>,
+    observers: Vec<Rc<RefCell<dyn SimulationObserver<'s> + 's>>>,
+    dead_letter_policy: DeadLetterPolicy,
+    dead_letters: Vec<(ErasedEvent, Route<'s>)>,
+    error_policy: ErrorPolicy,
+    steps_taken: usize,
+    entries_processed: usize,
+    livelock_limit: Option<usize>,
+    /// Time of the last processed entry and number of entries processed at it.
+    same_time_entries: (Time, usize),
+    /// Set if the simulation stopped before the next pending entry in
+    /// [Simulation::run_until_break].
+    stopped_at_break: bool,
 }
 
+// Fails to compile if Simulation becomes Send, so the documented contract is
+// revisited if that changes.
+const _: fn() = || {
+    trait AmbiguousIfSend<A> {
+        fn some_item() {}
+    }
+    impl<T: ?Sized> AmbiguousIfSend<()> for T {}
+    struct Invalid;
+    impl<T: ?Sized + Send> AmbiguousIfSend<Invalid> for T {}
+    let _ = <Simulation<'static> as AmbiguousIfSend<_>>::some_item;
+};
+
 impl<'s> Simulation<'s> {
     pub fn new(
-        #[cfg(feature = "rand")] rng: impl SimulationRng + 'static,
-        mut system: SystemModel<'s>,
+        #[cfg(any(feature = "rand", feature = "mini_rng"))] rng: impl SimulationRng + 'static,
+        system: SystemModel<'s>,
         initial_time: impl Into<Time>,
     ) -> Result<Self, SimulationError> {
-        system.validate()?;
+        Self::new_with_shared(
+            #[cfg(any(feature = "rand", feature = "mini_rng"))]
+            Rc::new(RefCell::new(rng)),
+            system,
+            initial_time.into(),
+            false,
+        )
+    }
 
-        #[cfg(feature = "rand")]
-        let global_rng = Rc::new(RefCell::new(rng));
-        let initial_time = initial_time.into();
-
-        let mut scheduler = Box::pin(Scheduler::new(initial_time));
-        for (id, mut model) in system.models.iter() {
-            let sim_ref = ModelCtx::new_parameterized(
-                &system.route_cache,
-                initial_time,
-                #[cfg(feature = "rand")]
-                global_rng.clone(),
-                id.clone(),
-                &mut scheduler,
-            );
+    pub fn builder() -> SimulationBuilder<'s> {
+        SimulationBuilder::new()
+    }
 
-            model.init(sim_ref)?;
-        }
+    pub(crate) fn new_with_shared(
+        #[cfg(any(feature = "rand", feature = "mini_rng"))] global_rng: Rc<
+            RefCell<dyn SimulationRng>,
+        >,
+        mut system: SystemModel<'s>,
+        initial_time: Time,
+        topological_init: bool,
+    ) -> Result<Self, SimulationError> {
+        system.validate()?;
 
-        Ok(Simulation {
-            #[cfg(feature = "rand")]
+        let init_order: Vec<CowStr<'s>> = if topological_init {
+            system.topological_order()?
+        } else {
+            system.models.keys().cloned().collect()
+        };
+
+        let mut simulation = Simulation {
+            #[cfg(any(feature = "rand", feature = "mini_rng"))]
             global_rng,
             system: Box::pin(system),
             initial_time,
-            scheduler,
-        })
+            init_order,
+            deferred_init: Rc::default(),
+            scheduler: Rc::new(RefCell::new(Scheduler::new(initial_time))),
+            metrics: None,
+            observers: vec![],
+            dead_letter_policy: DeadLetterPolicy::default(),
+            dead_letters: vec![],
+            error_policy: ErrorPolicy::default(),
+            steps_taken: 0,
+            entries_processed: 0,
+            livelock_limit: None,
+            same_time_entries: (initial_time, 0),
+            stopped_at_break: false,
+        };
+        simulation.init_models(false)?;
+        Ok(simulation)
+    }
+
+    /// Calls [Model::init] on all models, preceded by [Model::reset] if
+    /// `reset` is `true`.
+    fn init_models(&mut self, reset: bool) -> Result<(), SimulationError> {
+        for id in self.init_order.clone() {
+            let mut model = match self.system.models.borrow(id.clone())? {
+                Some(it) => it,
+                None => continue,
+            };
+            let init_failed = |source| SimulationError::InitFailed {
+                id: id.to_string(),
+                source: Box::new(source),
+            };
+
+            if reset {
+                model
+                    .reset(ModelCtx::new(self, id.clone()))
+                    .map_err(init_failed)?;
+            }
+            model
+                .init(ModelCtx::new(self, id.clone()))
+                .map_err(init_failed)?;
+        }
+        Ok(())
+    }
+
+    /// Calls [Model::init] on model with provided id.
+    ///
+    /// Allows starting models that deferred their initialization with
+    /// [ModelCtx::defer_init] before they receive any input.
+    pub fn init_model(&mut self, id: impl AsRef<str>) -> Result<(), SimulationError> {
+        let id = id.as_ref();
+        let mut model = self
+            .system
+            .models
+            .borrow(id.to_string())?
+            .ok_or_else(|| SimulationError::ModelNotFound { id: id.to_string() })?;
+        let id = model.id().clone();
+        self.deferred_init.borrow_mut().remove(&id);
+        let mut ctx = ModelCtx::new(self, id.clone());
+        ctx.starting = true;
+        model
+            .init(ctx)
+            .map_err(|source| SimulationError::InitFailed {
+                id: id.to_string(),
+                source: Box::new(source),
+            })
+    }
+
+    /// Restarts the simulation from its initial time without rebuilding the
+    /// system.
+    ///
+    /// All scheduled entries, dead letters and collected metrics are
+    /// discarded, then [Model::reset] and [Model::init] are called on every
+    /// model. Models must restore their own state in `reset`, otherwise they
+    /// continue with state left over from the previous run.
+    pub fn reset(&mut self) -> Result<(), SimulationError> {
+        *self.scheduler.borrow_mut() = Scheduler::new(self.initial_time);
+        self.deferred_init.borrow_mut().clear();
+        self.dead_letters.clear();
+        if self.metrics.is_some() {
+            self.metrics = Some(SimulationMetrics::new());
+        }
+        self.steps_taken = 0;
+        self.entries_processed = 0;
+        self.same_time_entries = (self.initial_time, 0);
+        self.stopped_at_break = false;
+        self.init_models(true)
     }
 
     #[inline]
@@ -77,10 +233,19 @@ impl<'s> Simulation<'s> {
         event: Event<M>,
         target: ConnectorPath<'s>,
     ) -> Result<(), SchedulerError> {
-        self.scheduler.schedule(
-            time.into(),
+        self.schedule_erased_event(time.into(), event.into(), target)
+    }
+
+    pub(crate) fn schedule_erased_event(
+        &mut self,
+        time: Time,
+        event: ErasedEvent,
+        target: ConnectorPath<'s>,
+    ) -> Result<(), SchedulerError> {
+        self.scheduler.borrow_mut().schedule(
+            time,
             Scheduled::Event {
-                event: event.into(),
+                event,
                 route: Route {
                     from: EventSource::External,
                     to: target,
@@ -89,10 +254,260 @@ impl<'s> Simulation<'s> {
         )
     }
 
+    /// Schedules a [Signal] to be delivered to `target` input connector.
+    #[inline]
+    pub fn schedule_signal(
+        &mut self,
+        time: impl Into<Time>,
+        target: ConnectorPath<'s>,
+    ) -> Result<(), SchedulerError> {
+        self.schedule_event(time, Signal(), target)
+    }
+
+    /// Schedules [Model::handle_update] of model with provided id, as if the
+    /// model scheduled it itself.
+    pub fn schedule_update(
+        &mut self,
+        time: impl Into<Time>,
+        model_id: impl ToCowStr<'s>,
+    ) -> Result<(), SimulationError> {
+        let model_id = model_id.to_cow_str();
+        if !self.system.models.ids().any(|it| it == model_id.as_ref()) {
+            return Err(SimulationError::ModelNotFound {
+                id: model_id.to_string(),
+            });
+        }
+        self.scheduler
+            .borrow_mut()
+            .schedule_update(time.into(), model_id)?;
+        Ok(())
+    }
+
+    /// Replaces the global RNG used by models from the next handled entry on.
+    ///
+    /// Models that sample from their own RNG (e.g. a `Generator` with an
+    /// overriding RNG) are unaffected.
+    #[cfg(any(feature = "rand", feature = "mini_rng"))]
+    pub fn set_rng(&mut self, rng: impl SimulationRng + 'static) {
+        self.global_rng = Rc::new(RefCell::new(rng));
+    }
+
     pub fn current_time(&self) -> Time {
-        self.scheduler.time
+        self.scheduler.borrow().time
+    }
+
+    /// Returns the time of the next scheduled entry, or `None` if there's
+    /// nothing left to process.
+    pub fn next_time(&self) -> Option<Time> {
+        self.scheduler.borrow().get_next_time()
+    }
+
+    /// Returns the time the simulation started at.
+    pub fn initial_time(&self) -> Time {
+        self.initial_time
+    }
+
+    /// Returns a snapshot of all pending work ordered by time.
+    pub fn timeline(&self) -> Vec<(Time, TimelineEntry<'s>)> {
+        self.scheduler
+            .borrow()
+            .timeline()
+            .flat_map(|(time, entries)| entries.iter().map(move |it| (time, it.into())))
+            .collect()
+    }
+
+    /// Returns the number of steps that processed at least one scheduled
+    /// entry.
+    pub fn steps_taken(&self) -> usize {
+        self.steps_taken
+    }
+
+    /// Returns the number of scheduled events and updates that were
+    /// processed.
+    pub fn entries_processed(&self) -> usize {
+        self.entries_processed
+    }
+
+    /// Enables collection of per-route event and per-model update counts.
+    pub fn enable_metrics(&mut self) {
+        if self.metrics.is_none() {
+            self.metrics = Some(SimulationMetrics::new());
+        }
+    }
+
+    /// Returns collected metrics if they were enabled with
+    /// [Simulation::enable_metrics].
+    pub fn metrics(&self) -> Option<&SimulationMetrics<'s>> {
+        self.metrics.as_ref()
+    }
+
+    /// Registers an observer notified of every processed entry.
+    ///
+    /// Observers are shared so that they can be inspected while the
+    /// simulation is running or after it's done.
+    pub fn add_observer(&mut self, observer: Rc<RefCell<dyn SimulationObserver<'s> + 's>>) {
+        self.observers.push(observer);
+    }
+
+    /// Enables livelock detection which makes [Simulation::step] return
+    /// [SimulationError::Livelock] if more than `limit` entries are processed
+    /// without time advancing; `None` disables it.
+    pub fn set_livelock_limit(&mut self, limit: Option<usize>) {
+        self.livelock_limit = limit;
+    }
+
+    pub fn set_dead_letter_policy(&mut self, policy: DeadLetterPolicy) {
+        self.dead_letter_policy = policy;
+    }
+
+    /// Sets how errors returned by model handlers are treated.
+    ///
+    /// Scheduler errors such as [SimulationError::Livelock] always abort the
+    /// simulation.
+    pub fn set_error_policy(&mut self, policy: ErrorPolicy) {
+        self.error_policy = policy;
+    }
+
+    /// Returns events which couldn't be delivered while using
+    /// [DeadLetterPolicy::Collect] policy.
+    pub fn dead_letters(&self) -> &[(ErasedEvent, Route<'s>)] {
+        &self.dead_letters
+    }
+
+    /// Takes ownership of collected dead letters, leaving an empty list.
+    pub fn take_dead_letters(&mut self) -> Vec<(ErasedEvent, Route<'s>)> {
+        std::mem::take(&mut self.dead_letters)
+    }
+
+    fn dead_letter(
+        &mut self,
+        event: ErasedEvent,
+        route: Route<'s>,
+        error: SimulationError,
+    ) -> Result<(), SimulationError> {
+        match self.dead_letter_policy {
+            DeadLetterPolicy::Abort => Err(error),
+            DeadLetterPolicy::Collect => {
+                self.dead_letters.push((event, route));
+                Ok(())
+            }
+            DeadLetterPolicy::Drop => Ok(()),
+        }
+    }
+
+    /// Borrows a model with provided id from the simulation.
+    ///
+    /// Returned model can be cast into its concrete type with
+    /// [BorrowedModel::cast] in order to read its state after the simulation
+    /// has been run.
+    pub fn borrow_model(
+        &mut self,
+        id: impl ToCowStr<'s>,
+    ) -> Result<Option<BorrowedModel<'s>>, SimulationError> {
+        Ok(self.system.models.borrow(id)?)
+    }
+
+    /// Returns serialized states of all models that support
+    /// [state snapshots](Model::state_snapshot), keyed by model id.
+    ///
+    /// Models that don't support snapshots are skipped.
+    #[cfg(feature = "serde")]
+    pub fn model_states(&self) -> Result<HashMap<String, serde_json::Value>, SimulationError> {
+        let mut result = HashMap::new();
+        for id in self.system.models.ids() {
+            let model = match self.system.models.get(id) {
+                Some(it) => it,
+                None => continue,
+            };
+            match model.state_snapshot() {
+                Ok(state) => {
+                    result.insert(id.to_string(), state);
+                }
+                Err(StateError::Unsupported) => {}
+                Err(source) => {
+                    return Err(SimulationError::State {
+                        model: id.to_string(),
+                        source,
+                    })
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Restores model states returned by [Simulation::model_states].
+    #[cfg(feature = "serde")]
+    pub fn load_model_states(
+        &mut self,
+        states: HashMap<String, serde_json::Value>,
+    ) -> Result<(), SimulationError> {
+        for (id, state) in states {
+            let model = self
+                .system
+                .models
+                .get_mut(&id)
+                .ok_or_else(|| SimulationError::ModelNotFound { id: id.clone() })?;
+            model
+                .restore_state_snapshot(state)
+                .map_err(|source| SimulationError::State { model: id, source })?;
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to a model with provided id if it's of type `M`.
+    pub fn inspect_model<M: Model<'s> + 'static>(&self, id: impl AsRef<str>) -> Option<&M> {
+        self.system.models.get_typed(id)
+    }
+
+    /// Returns a mutable reference to a model with provided id if it's of
+    /// type `M`.
+    pub fn inspect_model_mut<M: Model<'s> + 'static>(
+        &mut self,
+        id: impl AsRef<str>,
+    ) -> Option<&mut M> {
+        self.system.models.get_typed_mut(id)
     }
 
+    /// Returns mutable references to two distinct models with provided ids.
+    ///
+    /// Fails with [ModelStoreError::AliasedBorrow] if ids are the same and
+    /// with [SimulationError::ModelNotFound] if either model doesn't exist or
+    /// isn't of the requested type.
+    pub fn inspect_two_mut<A: Model<'s> + 'static, B: Model<'s> + 'static>(
+        &mut self,
+        id_a: impl AsRef<str>,
+        id_b: impl AsRef<str>,
+    ) -> Result<(&mut A, &mut B), SimulationError> {
+        let (id_a, id_b) = (id_a.as_ref(), id_b.as_ref());
+        if id_a == id_b {
+            return Err(ModelStoreError::AliasedBorrow {
+                id: id_a.to_string(),
+            }
+            .into());
+        }
+        let not_found = |id: &str| SimulationError::ModelNotFound { id: id.to_string() };
+
+        match self.system.models.get_two_typed_mut::<A, B>(id_a, id_b) {
+            (Some(a), Some(b)) => Ok((a, b)),
+            (None, _) => Err(not_found(id_a)),
+            (_, None) => Err(not_found(id_b)),
+        }
+    }
+
+    /// Iterates over all models of type `M` along with their ids.
+    pub fn models_of_type<'a, M: Model<'s> + 'static>(
+        &'a self,
+    ) -> impl Iterator<Item = (&'a str, &'a M)> + use<'a, 's, M> {
+        self.system.models.iter_typed()
+    }
+
+    /// Delivers an event to the model at the end of the route immediately.
+    ///
+    /// Events emitted by models through [ModelCtx] are never delivered
+    /// re-entrantly; they're queued and delivered after the emitting handler
+    /// returns, even when scheduled for the current time. Routing an event to
+    /// a model that's currently handling another one returns
+    /// [RoutingError::ReentrantDelivery].
     pub fn route_event(
         &mut self,
         event: ErasedEvent,
@@ -101,21 +516,44 @@ impl<'s> Simulation<'s> {
         let target_model = route.to.model.clone();
         let target_connector = route.to.connector.clone();
 
-        let model = self.system.models.borrow(target_model.clone())?.ok_or(
-            SimulationError::ModelNotFound {
-                id: target_model.to_string(),
-            },
-        )?;
+        let borrowed = match self.system.models.borrow(target_model.clone()) {
+            Err(ModelStoreError::ModelMissing) => {
+                return Err(RoutingError::ReentrantDelivery {
+                    model: target_model.to_string(),
+                    connector: target_connector.to_string(),
+                }
+                .into())
+            }
+            other => other?,
+        };
+        let model = match borrowed {
+            Some(it) => it,
+            None => {
+                let error = SimulationError::ModelNotFound {
+                    id: target_model.to_string(),
+                };
+                return self.dead_letter(event, route, error);
+            }
+        };
 
-        let handler = model
-            .get_input_handler_by_name(target_connector.as_ref())
-            .ok_or_else(|| RoutingError::UnknownModelConnector {
-                model: target_model.to_string(),
-                connector: target_connector.to_string(),
-            })?;
+        let handler = match model.get_input_handler_by_name(target_connector.as_ref()) {
+            Some(it) => it,
+            None => {
+                drop(model);
+                let error = RoutingError::UnknownModelConnector {
+                    model: target_model.to_string(),
+                    connector: target_connector.to_string(),
+                };
+                return self.dead_letter(event, route, error.into());
+            }
+        };
 
+        let event = self.system.coerce(event, handler.event_type_id());
+        let mut model_ctx = ModelCtx::new(self, target_model);
+        model_ctx.incoming_connector = Some(target_connector);
+        model_ctx.event_source = Some(route.from);
         let state = ConnectorCtx {
-            model_ctx: ModelCtx::new(self, target_model),
+            model_ctx,
             on_model: model,
         };
 
@@ -123,44 +561,188 @@ impl<'s> Simulation<'s> {
         Ok(())
     }
 
+    /// Processes all entries scheduled for the next time.
+    ///
+    /// Entries are processed in the order they were scheduled in, except
+    /// events delivered to the same model which are applied in order of
+    /// descending [input connector priority](crate::routes::InputConnectorInfo::with_priority).
+    /// Entries scheduled for current time while handling the step are
+    /// processed by the following step; use [Simulation::step_time] to
+    /// process them as well.
     pub fn step(&mut self) -> Result<(), SimulationError> {
-        let scheduled = match self.scheduler.next() {
+        self.step_with_break(&mut |_| false)?;
+        Ok(())
+    }
+
+    /// Processes entries like [Simulation::step], but stops before the first
+    /// entry `pred` returns `true` for.
+    ///
+    /// Returns `true` if processing was stopped. Stopped steps are continued
+    /// by the following step, which processes the entry it stopped at without
+    /// checking it again.
+    fn step_with_break(
+        &mut self,
+        pred: &mut dyn FnMut(&BreakContext<'_, 's>) -> bool,
+    ) -> Result<bool, SimulationError> {
+        {
+            let mut scheduler = self.scheduler.borrow_mut();
+            let resumed = !scheduler.pending.is_empty();
+            if !scheduler.advance() {
+                return Ok(false);
+            }
+            if !resumed {
+                scheduler.order_pending(&self.system.input_priorities);
+                self.steps_taken += 1;
+            }
+        }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("step", time = %self.current_time()).entered();
+
+        loop {
+            let resuming = std::mem::take(&mut self.stopped_at_break);
+            let stop = {
+                let scheduler = self.scheduler.borrow();
+                match scheduler.pending.front() {
+                    Some(entry) => {
+                        !resuming
+                            && pred(&BreakContext {
+                                simulation: self,
+                                time: scheduler.time,
+                                entry,
+                            })
+                    }
+                    None => break,
+                }
+            };
+            if stop {
+                self.stopped_at_break = true;
+                return Ok(true);
+            }
+
+            let entry = match self.scheduler.borrow_mut().pending.pop_front() {
+                Some(it) => it,
+                None => break,
+            };
+            self.entries_processed += 1;
+
+            if let Some(limit) = self.livelock_limit {
+                let time = self.current_time();
+                if self.same_time_entries.0 == time {
+                    self.same_time_entries.1 += 1;
+                } else {
+                    self.same_time_entries = (time, 1);
+                }
+
+                if self.same_time_entries.1 > limit {
+                    let model = match &entry {
+                        Scheduled::Internal(model, _) => model.to_string(),
+                        Scheduled::Event { route, .. } => route.to.model.to_string(),
+                    };
+                    return Err(SimulationError::Livelock { model, time, limit });
+                }
+            }
+
+            if let Err(error) = self.process_entry(entry) {
+                match &mut self.error_policy {
+                    ErrorPolicy::Abort => return Err(error),
+                    ErrorPolicy::Continue(handler) => handler(&error),
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Processes all entries scheduled for the next time, including ones
+    /// scheduled for that same time while processing it.
+    ///
+    /// Returns the time the simulation advanced to, or `None` if there was
+    /// nothing left to process. Models that keep scheduling work for current
+    /// time prevent this from returning unless a
+    /// [livelock limit](Simulation::set_livelock_limit) is set.
+    pub fn step_time(&mut self) -> Result<Option<Time>, SimulationError> {
+        let time = match self.scheduler.borrow().get_next_time() {
             Some(it) => it,
-            None => return Ok(()),
+            None => return Ok(None),
         };
+        while self.scheduler.borrow().get_next_time() == Some(time) {
+            self.step()?;
+        }
+        Ok(Some(time))
+    }
 
-        for entry in scheduled {
-            match entry {
-                Scheduled::Internal(model_id) => {
-                    let mut model = self.system.models.borrow(model_id.clone())?.ok_or(
-                        SimulationError::ModelNotFound {
-                            id: model_id.to_string(),
-                        },
-                    )?;
+    fn process_entry(&mut self, entry: Scheduled<'s>) -> Result<(), SimulationError> {
+        if !self.observers.is_empty() {
+            let time = self.current_time();
+            let summary = TimelineEntry::from(&entry);
+            for observer in &self.observers {
+                observer.borrow_mut().observe(time, &summary);
+            }
+        }
 
-                    let state = ModelCtx::new(self, model_id);
+        #[cfg(debug_assertions)]
+        let target = match &entry {
+            Scheduled::Internal(model_id, _) => model_id.clone(),
+            Scheduled::Event { route, .. } => route.to.model.clone(),
+        };
 
-                    model.handle_update(state)?;
+        let result = match entry {
+            Scheduled::Internal(model_id, token) => {
+                if let Some(metrics) = &mut self.metrics {
+                    metrics.record_update(&model_id);
                 }
-                Scheduled::Event { event, route } => {
-                    self.route_event(event, route)?;
+                #[cfg(feature = "tracing")]
+                tracing::trace!(model = %model_id, token, "internal update");
+
+                let mut model = self.system.models.borrow(model_id.clone())?.ok_or(
+                    SimulationError::ModelNotFound {
+                        id: model_id.to_string(),
+                    },
+                )?;
+
+                let mut state = ModelCtx::new(self, model_id);
+                state.update_token = token;
+
+                model.handle_update(state)
+            }
+            Scheduled::Event { event, route } => {
+                if let Some(metrics) = &mut self.metrics {
+                    metrics.record_event(&route);
+                }
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    source = ?route.from,
+                    model = %route.to.model,
+                    connector = %route.to.connector,
+                    event_type = event.type_name(),
+                    "event"
+                );
+
+                let deferred = self.deferred_init.borrow().contains(&route.to.model);
+                if deferred {
+                    self.init_model(route.to.model.clone())?;
                 }
+                self.route_event(event, route)
             }
-        }
+        };
 
-        Ok(())
+        #[cfg(debug_assertions)]
+        if result.is_ok() {
+            self.system.check_connectors(&target)?;
+        }
+        result
     }
 
     /// Runs simulation until passed time is reached (inclusive) or the simulated system becomes inert
     pub fn run_until(&mut self, time: impl Into<Time>) -> Result<(), SimulationError> {
         let max_time = time.into();
 
-        while let Some(expected_time) = self.scheduler.get_next_time() {
-            if expected_time >= max_time {
-                break;
+        loop {
+            let expected_time = self.scheduler.borrow().get_next_time();
+            match expected_time {
+                Some(it) if it < max_time => self.step()?,
+                _ => break,
             }
-
-            self.step()?;
         }
 
         Ok(())
@@ -170,15 +752,284 @@ impl<'s> Simulation<'s> {
     pub fn run(&mut self) -> Result<(), SimulationError> {
         self.run_until(Time::MAX)
     }
+
+    /// Runs simulation until `pred` returns `true` for the entry about to be
+    /// processed, or the simulated system becomes inert.
+    ///
+    /// Simulation stops before processing the entry `pred` returned `true`
+    /// for and can be inspected and resumed afterwards. Resuming, either with
+    /// this or any other run function, processes that entry first without
+    /// checking it again.
+    ///
+    /// Returns `true` if the simulation was stopped by `pred`.
+    pub fn run_until_break(
+        &mut self,
+        mut pred: impl FnMut(&BreakContext<'_, 's>) -> bool,
+    ) -> Result<bool, SimulationError> {
+        while self.scheduler.borrow().get_next_time().is_some() {
+            if self.step_with_break(&mut pred)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Runs simulation until it becomes inert or `stop` is set, pacing steps
+    /// so that simulated time advances `scale` times faster than wall-clock
+    /// time.
+    ///
+    /// Pacing is best-effort: steps that take longer than the time between
+    /// them delay the simulation, which then runs without sleeping until it
+    /// catches up.
+    ///
+    /// Returns [SimulationError::InvalidRealtimeScale] if `scale` isn't a
+    /// finite positive number.
+    pub fn run_realtime(&mut self, scale: f64, stop: &AtomicBool) -> Result<(), SimulationError> {
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(SimulationError::InvalidRealtimeScale { scale });
+        }
+        // longest uninterrupted sleep so that stop flag is checked regularly
+        const MAX_SLEEP: Duration = Duration::from_millis(50);
+
+        let start_time = self.current_time();
+        let start = Instant::now();
+        loop {
+            let next_time = match self.scheduler.borrow().get_next_time() {
+                Some(it) => it,
+                None => break,
+            };
+            let offset = (next_time - start_time).as_secs_f64() / scale;
+            let target = start + Duration::from_secs_f64(offset.max(0.0));
+            loop {
+                if stop.load(Ordering::Relaxed) {
+                    return Ok(());
+                }
+                let remaining = target.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                std::thread::sleep(remaining.min(MAX_SLEEP));
+            }
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Runs simulation while scheduling external events received through `rx`
+    /// between steps.
+    ///
+    /// Events are sent as `Event<M>` (or anything else convertible into
+    /// [ErasedEvent]) so that they can be produced on another thread when `M`
+    /// is `Send`. Received events scheduled before current time are dropped
+    /// with a warning.
+    ///
+    /// If there's no scheduled work left, this blocks until an event is
+    /// received; it returns once that's the case and all senders were dropped.
+    pub fn run_interactive<E: Into<ErasedEvent>>(
+        &mut self,
+        rx: std::sync::mpsc::Receiver<(Time, E, ConnectorPath<'s>)>,
+    ) -> Result<(), SimulationError> {
+        let mut disconnected = false;
+        loop {
+            loop {
+                let (time, event, target) = match rx.try_recv() {
+                    Ok(it) => it,
+                    Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        disconnected = true;
+                        break;
+                    }
+                };
+                self.schedule_interactive(time, event.into(), target)?;
+            }
+
+            if self.scheduler.borrow().get_next_time().is_some() {
+                self.step()?;
+            } else if disconnected {
+                return Ok(());
+            } else {
+                match rx.recv() {
+                    Ok((time, event, target)) => {
+                        self.schedule_interactive(time, event.into(), target)?
+                    }
+                    Err(_) => return Ok(()),
+                }
+            }
+        }
+    }
+
+    fn schedule_interactive(
+        &mut self,
+        time: Time,
+        event: ErasedEvent,
+        target: ConnectorPath<'s>,
+    ) -> Result<(), SimulationError> {
+        let current = self.current_time();
+        if time < current {
+            log::warn!(
+                "dropping event for {:?} scheduled at {} which is before current time {}",
+                target,
+                time,
+                current
+            );
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                target = ?target,
+                %time,
+                %current,
+                "dropping event scheduled before current time"
+            );
+            return Ok(());
+        }
+        self.scheduler.borrow_mut().schedule(
+            time,
+            Scheduled::Event {
+                event,
+                route: Route::new_external(target),
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Runs simulation until the simulated system becomes inert, or returns
+    /// [SimulationError::StepLimitExceeded] if it's still producing entries
+    /// after `max_steps` steps.
+    pub fn run_with_guard(&mut self, max_steps: usize) -> Result<(), SimulationError> {
+        for _ in 0..max_steps {
+            if self.scheduler.borrow().get_next_time().is_none() {
+                return Ok(());
+            }
+            self.step()?;
+        }
+
+        if self.scheduler.borrow().get_next_time().is_some() {
+            return Err(SimulationError::StepLimitExceeded { limit: max_steps });
+        }
+        Ok(())
+    }
+}
+
+/// Runs a fresh system produced by `build` once for every seed until `until`
+/// and collects results returned by `extract` from finished simulations.
+///
+/// Runs are seeded with `rand::rngs::StdRng` when `rand` feature is enabled
+/// and [MiniRng](crate::util::MiniRng) otherwise. Each run owns its system,
+/// so results only depend on the seed.
+#[cfg(any(feature = "rand", feature = "mini_rng"))]
+pub fn run_ensemble<'s, F, E, R>(
+    build: F,
+    seeds: &[u64],
+    until: impl Into<Time>,
+    mut extract: E,
+) -> Result<Vec<R>, SimulationError>
+where
+    F: Fn() -> SystemModel<'s>,
+    E: FnMut(&Simulation<'s>) -> R,
+{
+    let until = until.into();
+    let mut results = Vec::with_capacity(seeds.len());
+    for seed in seeds {
+        #[cfg(feature = "rand")]
+        let rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(*seed);
+        #[cfg(not(feature = "rand"))]
+        let rng = crate::util::MiniRng::seed_from_u64(*seed);
+
+        let mut simulation = Simulation::builder().system(build()).rng(rng).build()?;
+        simulation.run_until(until)?;
+        results.push(extract(&simulation));
+    }
+    Ok(results)
+}
+
+/// Constructs a [Simulation] the same way regardless of enabled features.
+pub struct SimulationBuilder<'s> {
+    system: SystemModel<'s>,
+    initial_time: Time,
+    #[cfg(any(feature = "rand", feature = "mini_rng"))]
+    rng: Option<Rc<RefCell<dyn SimulationRng>>>,
+    topological_init: bool,
+}
+
+impl<'s> Default for SimulationBuilder<'s> {
+    fn default() -> Self {
+        SimulationBuilder::new()
+    }
+}
+
+impl<'s> SimulationBuilder<'s> {
+    pub fn new() -> Self {
+        SimulationBuilder {
+            system: SystemModel::new(),
+            initial_time: Time::default(),
+            #[cfg(any(feature = "rand", feature = "mini_rng"))]
+            rng: None,
+            topological_init: false,
+        }
+    }
+
+    pub fn system(mut self, system: SystemModel<'s>) -> Self {
+        self.system = system;
+        self
+    }
+
+    pub fn initial_time(mut self, initial_time: impl Into<Time>) -> Self {
+        self.initial_time = initial_time.into();
+        self
+    }
+
+    /// Sets the global RNG.
+    ///
+    /// If none is provided, `rand::thread_rng` is used with `rand` feature and
+    /// `MiniRng` with a fixed seed otherwise.
+    #[cfg(any(feature = "rand", feature = "mini_rng"))]
+    pub fn rng(mut self, rng: impl SimulationRng + 'static) -> Self {
+        self.rng = Some(Rc::new(RefCell::new(rng)));
+        self
+    }
+
+    /// Initializes models in [topological order](SystemModel::topological_order)
+    /// of routes instead of insertion order.
+    ///
+    /// Building fails if routes form a cycle.
+    pub fn topological_init(mut self, enabled: bool) -> Self {
+        self.topological_init = enabled;
+        self
+    }
+
+    pub fn build(self) -> Result<Simulation<'s>, SimulationError> {
+        Simulation::new_with_shared(
+            #[cfg(feature = "rand")]
+            self.rng
+                .unwrap_or_else(|| Rc::new(RefCell::new(rand::thread_rng()))),
+            #[cfg(all(feature = "mini_rng", not(feature = "rand")))]
+            self.rng
+                .unwrap_or_else(|| Rc::new(RefCell::new(crate::util::MiniRng::default()))),
+            self.system,
+            self.initial_time,
+            self.topological_init,
+        )
+    }
 }
 
 pub struct ModelCtx<'s> {
     pub time: Time,
-    #[cfg(feature = "rand")]
+    /// Time the simulation started at.
+    pub initial_time: Time,
+    #[cfg(any(feature = "rand", feature = "mini_rng"))]
     pub rng: Rc<RefCell<dyn SimulationRng>>,
     pub model_id: CowStr<'s>,
     pub routes: AdjacentModels<'s>,
-    pub scheduler: *mut Pin<Box<Scheduler<'s>>>,
+    pub scheduler: Rc<RefCell<Scheduler<'s>>>,
+    deferred_init: Rc<RefCell<HashSet<CowStr<'s>>>>,
+    /// Whether the model is being started after deferring its initialization.
+    starting: bool,
+    /// Token provided to [ModelCtx::schedule_update_with_token] when the
+    /// update currently being handled was scheduled.
+    pub update_token: Option<u64>,
+    /// Input connector the event currently being handled arrived on.
+    pub incoming_connector: Option<CowStr<'s>>,
+    /// Source of the event currently being handled.
+    pub event_source: Option<EventSource<'s>>,
 }
 
 impl<'s> ModelCtx<'s> {
@@ -190,41 +1041,78 @@ impl<'s> ModelCtx<'s> {
             .cloned()
             .unwrap_or_default();
 
-        let scheduler: *mut Pin<Box<Scheduler<'s>>> = &mut simulation.scheduler;
-
         ModelCtx {
             time: simulation.current_time(),
-            #[cfg(feature = "rand")]
+            initial_time: simulation.initial_time,
+            #[cfg(any(feature = "rand", feature = "mini_rng"))]
             rng: simulation.global_rng.clone(),
             model_id: model,
             routes,
-            scheduler,
+            scheduler: simulation.scheduler.clone(),
+            deferred_init: simulation.deferred_init.clone(),
+            starting: false,
+            update_token: None,
+            incoming_connector: None,
+            event_source: None,
         }
     }
 
-    fn new_parameterized(
-        route_cache: &HashMap<CowStr<'s>, AdjacentModels<'s>>,
-        time: Time,
-        #[cfg(feature = "rand")] rng: Rc<RefCell<dyn SimulationRng>>,
-        model: CowStr<'s>,
-        scheduler: &mut Pin<Box<Scheduler<'s>>>,
-    ) -> Self {
-        let routes = route_cache.get(model.as_ref()).cloned().unwrap_or_default();
+    pub fn model_id(&self) -> &CowStr<'s> {
+        &self.model_id
+    }
 
-        let scheduler: *mut Pin<Box<Scheduler<'s>>> = scheduler;
+    /// Returns time elapsed since the start of the simulation.
+    pub fn elapsed(&self) -> TimeDelta {
+        self.time - self.initial_time
+    }
 
-        ModelCtx {
-            time,
-            #[cfg(feature = "rand")]
-            rng,
-            model_id: model,
-            routes,
-            scheduler,
+    /// Defers initialization of this model until it receives its first event.
+    ///
+    /// Meant to be called from [Model::init], which is then called again right
+    /// before the first event is handled or when the model is started with
+    /// [Simulation::init_model]. Returns `false` if the model is being started
+    /// that way, in which case `init` should schedule its work as usual.
+    pub fn defer_init(&self) -> bool {
+        if self.starting {
+            return false;
         }
+        self.deferred_init
+            .borrow_mut()
+            .insert(self.model_id.clone());
+        true
     }
 
-    pub fn model_id(&self) -> &CowStr<'s> {
-        &self.model_id
+    /// Returns the token the handled update was scheduled with, or `None` if
+    /// it was scheduled without one or the model isn't handling an update.
+    pub fn update_token(&self) -> Option<u64> {
+        self.update_token
+    }
+
+    /// Returns the name of the input connector the handled event arrived on,
+    /// or `None` if the model isn't handling an event.
+    ///
+    /// Useful for models with many homogeneous inputs that share a handler.
+    pub fn incoming_connector(&self) -> Option<&str> {
+        self.incoming_connector.as_deref()
+    }
+
+    /// Returns the source of the handled event, or `None` if the model isn't
+    /// handling an event.
+    pub fn event_source(&self) -> Option<&EventSource<'s>> {
+        self.event_source.as_ref()
+    }
+
+    /// Returns `true` if provided output connector is connected to an input.
+    ///
+    /// Events pushed to unconnected outputs are dropped, so this can be used
+    /// to skip constructing them.
+    pub fn is_connected(&self, output_connector: impl AsRef<str>) -> bool {
+        self.routes.outputs.iter().any(|route| {
+            route
+                .from_connection()
+                .map(|from| from.connector.as_ref() == output_connector.as_ref())
+                .unwrap_or_default()
+        })
     }
 
     #[cfg(feature = "rand")]
@@ -244,23 +1132,73 @@ impl<'s> ModelCtx<'s> {
         self.rng.borrow_mut().gen_range(range)
     }
 
+    #[cfg(all(feature = "mini_rng", not(feature = "rand")))]
+    pub fn rand<T: MiniSample>(&self) -> T {
+        T::sample(&mut *self.rng.borrow_mut())
+    }
+
+    #[cfg(all(feature = "mini_rng", not(feature = "rand")))]
+    pub fn rand_range<T: MiniSample>(&self, range: std::ops::Range<T>) -> T {
+        T::sample_range(&mut *self.rng.borrow_mut(), range)
+    }
+
     pub fn cancel_updates(&self) {
-        unsafe {
-            (*self.scheduler).cancel_updates(self.model_id().clone(), None);
-        }
+        self.scheduler
+            .borrow_mut()
+            .cancel_updates(self.model_id().clone(), None);
     }
 
     pub fn cancel_updates_bounded(&self, range: TimeBounds) {
-        unsafe {
-            (*self.scheduler).cancel_updates(self.model_id().clone(), Some(range));
-        }
+        self.scheduler
+            .borrow_mut()
+            .cancel_updates(self.model_id().clone(), Some(range));
     }
 
     pub fn schedule_update(&self, time: TimeTrigger) -> Result<(), SimulationError> {
-        unsafe {
-            (*self.scheduler)
-                .schedule_update(time.to_discrete(self.time), self.model_id().clone())?;
-        }
+        self.scheduler
+            .borrow_mut()
+            .schedule_update(time.to_discrete(self.time), self.model_id().clone())?;
+        Ok(())
+    }
+
+    /// Returns the time of the soonest pending update of model with provided
+    /// id, or `None` if it has no updates scheduled.
+    ///
+    /// Allows models to coordinate their timing without exchanging events.
+    pub fn next_update_of(&self, model: impl AsRef<str>) -> Option<Time> {
+        self.scheduler.borrow().next_update_of(model)
+    }
+
+    /// Removes and returns events of type `M` that are scheduled to be delivered
+    /// to `input_connector` of this model at current time.
+    ///
+    /// Drained events won't be passed to the input handler again. Scheduler
+    /// delivers entries with equal time in the order they were scheduled in,
+    /// and drained events are returned in that same order, so all of them come
+    /// after the event that's currently being handled.
+    pub fn drain_same_time_inputs<M: Message>(
+        &self,
+        input_connector: impl AsRef<str>,
+    ) -> Result<Vec<Event<M>>, SimulationError> {
+        let events = self
+            .scheduler
+            .borrow_mut()
+            .drain_events_to::<M>(self.model_id.as_ref(), input_connector.as_ref())?;
+        Ok(events)
+    }
+
+    /// Schedules an update tagged with a `token` that can be read through
+    /// [ModelCtx::update_token] while handling it.
+    pub fn schedule_update_with_token(
+        &self,
+        time: TimeTrigger,
+        token: u64,
+    ) -> Result<(), SimulationError> {
+        self.scheduler.borrow_mut().schedule_update_with_token(
+            time.to_discrete(self.time),
+            self.model_id().clone(),
+            token,
+        )?;
         Ok(())
     }
 
@@ -270,24 +1208,50 @@ impl<'s> ModelCtx<'s> {
         output_connector: CowStr<'s>,
         time: TimeTrigger,
     ) -> Result<(), SimulationError> {
+        self.push_erased_event(event.erase_message_type(), output_connector, time)?;
+        Ok(())
+    }
+
+    /// Sends an event through provided output connector like
+    /// [ModelCtx::push_event_with_time] and reports how many inputs it was
+    /// scheduled to be delivered to.
+    ///
+    /// Allows source models to throttle themselves when nothing is connected.
+    pub fn try_push_event<M: Message>(
+        &self,
+        event: Event<M>,
+        output_connector: CowStr<'s>,
+        time: TimeTrigger,
+    ) -> Result<DeliveryReport, SimulationError> {
+        self.push_erased_event(event.erase_message_type(), output_connector, time)
+    }
+
+    pub(crate) fn push_erased_event(
+        &self,
+        mut event: ErasedEvent,
+        output_connector: CowStr<'s>,
+        time: TimeTrigger,
+    ) -> Result<DeliveryReport, SimulationError> {
         let target = match self.routes.adjacent_input(output_connector.clone()) {
             Some(first) => first,
-            _ => return Ok(()),
+            _ => return Ok(DeliveryReport { delivered: 0 }),
         };
 
+        if let Some(map) = self.routes.route_map(&output_connector) {
+            event = map.apply(event);
+        }
+
         let from = EventSource::Model(ConnectorPath {
             model: self.model_id().clone(),
             connector: output_connector,
         });
 
-        unsafe {
-            (*self.scheduler).schedule_event(
-                time.to_discrete(self.time),
-                event.erase_message_type(),
-                Route { from, to: target },
-            )?;
-        }
-        Ok(())
+        self.scheduler.borrow_mut().schedule_event(
+            time.to_discrete(self.time),
+            event,
+            Route { from, to: target },
+        )?;
+        Ok(DeliveryReport { delivered: 1 })
     }
 
     #[inline(always)]
@@ -299,25 +1263,39 @@ impl<'s> ModelCtx<'s> {
         self.push_event_with_time(event, source_connector, TimeTrigger::Absolute(self.time))
     }
 
+    /// Sends a [Signal] through provided output connector at current time.
+    #[inline]
+    pub fn emit_signal(&self, output_connector: impl ToCowStr<'s>) -> Result<(), SimulationError> {
+        self.push_event(Signal(), output_connector.to_cow_str())
+    }
+
+    /// Sends a [Signal] through provided output connector at `time`.
+    #[inline]
+    pub fn emit_signal_at(
+        &self,
+        output_connector: impl ToCowStr<'s>,
+        time: TimeTrigger,
+    ) -> Result<(), SimulationError> {
+        self.push_event_with_time(Signal(), output_connector.to_cow_str(), time)
+    }
+
     pub fn internal_event_with_time<M: Message>(
         &self,
         event: Event<M>,
         target_connector: CowStr<'s>,
         time: TimeTrigger,
     ) -> Result<(), SimulationError> {
-        unsafe {
-            (*self.scheduler).schedule_event(
-                time.to_discrete(self.time),
-                event.erase_message_type(),
-                Route {
-                    from: EventSource::Internal,
-                    to: ConnectorPath {
-                        model: self.model_id().clone(),
-                        connector: target_connector,
-                    },
+        self.scheduler.borrow_mut().schedule_event(
+            time.to_discrete(self.time),
+            event.erase_message_type(),
+            Route {
+                from: EventSource::Internal,
+                to: ConnectorPath {
+                    model: self.model_id().clone(),
+                    connector: target_connector,
                 },
-            )?;
-        }
+            },
+        )?;
         Ok(())
     }
 
@@ -331,22 +1309,111 @@ impl<'s> ModelCtx<'s> {
     }
 }
 
+/// Outcome of sending an event with [ModelCtx::try_push_event].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DeliveryReport {
+    /// Number of input connectors the event was scheduled to be delivered to.
+    pub delivered: usize,
+}
+
+impl DeliveryReport {
+    /// Returns `true` if the event was sent to at least one input connector.
+    pub fn is_delivered(&self) -> bool {
+        self.delivered > 0
+    }
+}
+
+/// State passed to the predicate of [Simulation::run_until_break].
+pub struct BreakContext<'a, 's> {
+    simulation: &'a Simulation<'s>,
+    time: Time,
+    entry: &'a Scheduled<'s>,
+}
+
+impl<'a, 's> BreakContext<'a, 's> {
+    /// Returns the current simulation time.
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    /// Returns the entry about to be processed.
+    pub fn entry(&self) -> &'a Scheduled<'s> {
+        self.entry
+    }
+
+    /// Returns the simulation, allowing model state to be inspected.
+    pub fn simulation(&self) -> &'a Simulation<'s> {
+        self.simulation
+    }
+
+    /// Returns `true` if the next entry is an event delivered to provided
+    /// input connector.
+    pub fn is_event_to(&self, model: impl AsRef<str>, connector: impl AsRef<str>) -> bool {
+        match self.entry {
+            Scheduled::Event { route, .. } => {
+                route.to.model.as_ref() == model.as_ref()
+                    && route.to.connector.as_ref() == connector.as_ref()
+            }
+            Scheduled::Internal(..) => false,
+        }
+    }
+}
+
 pub struct ConnectorCtx<'s> {
     pub(crate) model_ctx: ModelCtx<'s>,
     pub(crate) on_model: BorrowedModel<'s>,
 }
 
 pub enum Scheduled<'s> {
-    Internal(CowStr<'s>),
+    /// Model update with an optional user provided token.
+    Internal(CowStr<'s>, Option<u64>),
     Event {
         event: ErasedEvent,
         route: Route<'s>,
     },
 }
 
+/// Owned summary of a [Scheduled] entry returned by [Simulation::timeline].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimelineEntry<'s> {
+    Update {
+        model: CowStr<'s>,
+        token: Option<u64>,
+    },
+    Event {
+        route: Route<'s>,
+        type_name: &'static str,
+    },
+}
+
+impl<'s> From<&Scheduled<'s>> for TimelineEntry<'s> {
+    fn from(value: &Scheduled<'s>) -> Self {
+        match value {
+            Scheduled::Internal(model, token) => TimelineEntry::Update {
+                model: model.clone(),
+                token: *token,
+            },
+            Scheduled::Event { event, route } => TimelineEntry::Event {
+                route: route.clone(),
+                type_name: event.type_name(),
+            },
+        }
+    }
+}
+
+/// Orders scheduled entries by time.
+///
+/// Entries with equal time are kept in the order they were scheduled in and
+/// are processed in that order. Removing entries (e.g. cancelling updates)
+/// preserves the order of remaining ones, so same-time ordering only depends
+/// on the order of scheduling calls.
 pub struct Scheduler<'s> {
     pub time: Time,
     scheduled: BTreeMap<Time, Vec<Scheduled<'s>>>,
+    /// Entries scheduled for current time that weren't processed yet.
+    pending: VecDeque<Scheduled<'s>>,
+    /// Times at which each model has scheduled updates in `scheduled`.
+    update_times: HashMap<CowStr<'s>, BTreeSet<Time>>,
 }
 
 impl<'s> Scheduler<'s> {
@@ -354,10 +1421,127 @@ impl<'s> Scheduler<'s> {
         Scheduler {
             time: current_time,
             scheduled: BTreeMap::new(),
+            pending: VecDeque::new(),
+            update_times: HashMap::new(),
         }
     }
 
+    /// Removes `time` from update index of models updated by `entries`.
+    fn unindex_updates(&mut self, time: &Time, entries: &[Scheduled<'s>]) {
+        for entry in entries {
+            if let Scheduled::Internal(model, _) = entry {
+                if let Some(times) = self.update_times.get_mut(model) {
+                    times.remove(time);
+                    if times.is_empty() {
+                        self.update_times.remove(model);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves entries scheduled for the next time into pending entries if
+    /// there are none left.
+    ///
+    /// Returns `false` if there's nothing left to process.
+    fn advance(&mut self) -> bool {
+        if !self.pending.is_empty() {
+            return true;
+        }
+        match self.scheduled.pop_first() {
+            Some((time, entries)) => {
+                self.unindex_updates(&time, &entries);
+                self.time = time;
+                self.pending = entries.into();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reorders pending events delivered to the same model by descending
+    /// priority of their target connectors, keeping positions occupied by
+    /// events of each model.
+    fn order_pending(&mut self, priorities: &HashMap<ConnectorPath<'s>, i32>) {
+        if priorities.is_empty() || self.pending.len() < 2 {
+            return;
+        }
+        let priority = |entry: &Scheduled<'s>| match entry {
+            Scheduled::Event { route, .. } => priorities.get(&route.to).copied().unwrap_or(0),
+            Scheduled::Internal(..) => 0,
+        };
+
+        let mut positions: HashMap<CowStr<'s>, Vec<usize>> = HashMap::new();
+        for (i, entry) in self.pending.iter().enumerate() {
+            if let Scheduled::Event { route, .. } = entry {
+                positions.entry(route.to.model.clone()).or_default().push(i);
+            }
+        }
+
+        let mut slots: Vec<Option<Scheduled<'s>>> = self.pending.drain(..).map(Some).collect();
+        for indices in positions.values().filter(|it| it.len() > 1) {
+            let mut events: Vec<Scheduled<'s>> =
+                indices.iter().filter_map(|i| slots[*i].take()).collect();
+            events.sort_by_key(|it| std::cmp::Reverse(priority(it)));
+            for (i, event) in indices.iter().zip(events) {
+                slots[*i] = Some(event);
+            }
+        }
+        self.pending = slots.into_iter().flatten().collect();
+    }
+
+    fn drain_events_to<M: Message>(
+        &mut self,
+        model: &str,
+        connector: &str,
+    ) -> Result<Vec<Event<M>>, RoutingError> {
+        let targets = |entry: &Scheduled| match entry {
+            Scheduled::Event { route, .. } => {
+                route.to.model.as_ref() == model && route.to.connector.as_ref() == connector
+            }
+            _ => false,
+        };
+
+        let current = self.scheduled.get(&self.time).into_iter().flatten();
+        for entry in self.pending.iter().chain(current) {
+            if let Scheduled::Event { event, .. } = entry {
+                if targets(entry) && event.peek::<M>().is_none() {
+                    return Err(RoutingError::InvalidEventType {
+                        event_type: event.type_name,
+                        expected: std::any::type_name::<M>(),
+                    });
+                }
+            }
+        }
+
+        let (mut drained, pending): (VecDeque<_>, VecDeque<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(targets);
+        self.pending = pending;
+        if let Some(current) = self.scheduled.get_mut(&self.time) {
+            let (current_drained, kept): (Vec<_>, Vec<_>) =
+                std::mem::take(current).into_iter().partition(targets);
+            drained.extend(current_drained);
+            if kept.is_empty() {
+                self.scheduled.remove(&self.time);
+            } else {
+                *current = kept;
+            }
+        }
+
+        Ok(drained
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Scheduled::Event { event, .. } => event.try_restore_type::<M>().ok(),
+                _ => None,
+            })
+            .collect())
+    }
+
     fn schedule(&mut self, time: Time, value: Scheduled<'s>) -> Result<(), SchedulerError> {
+        if !time.is_valid() {
+            return Err(SchedulerError::InvalidTime(time));
+        }
         if time < self.time {
             return Err(SchedulerError::TimeRegression {
                 current: self.time.clone(),
@@ -365,6 +1549,13 @@ impl<'s> Scheduler<'s> {
             });
         }
 
+        if let Scheduled::Internal(model, _) = &value {
+            self.update_times
+                .entry(model.clone())
+                .or_default()
+                .insert(time);
+        }
+
         match self.scheduled.get_mut(&time) {
             Some(events) => {
                 events.push(value);
@@ -377,6 +1568,22 @@ impl<'s> Scheduler<'s> {
         Ok(())
     }
 
+    /// Returns the time of the soonest pending update of provided model.
+    pub fn next_update_of(&self, model: impl AsRef<str>) -> Option<Time> {
+        let model = model.as_ref();
+        let pending = self
+            .pending
+            .iter()
+            .any(|it| matches!(it, Scheduled::Internal(id, _) if id.as_ref() == model));
+        if pending {
+            return Some(self.time);
+        }
+        self.update_times
+            .get(model)
+            .and_then(|times| times.first())
+            .copied()
+    }
+
     pub fn cancel_updates(&mut self, model: impl ToCowStr<'s>, bounded: Option<TimeBounds>) {
         let model = model.to_cow_str();
 
@@ -384,7 +1591,7 @@ impl<'s> Scheduler<'s> {
             let mut occurences = vec![];
             for (i, it) in entries.iter().enumerate() {
                 match it {
-                    Scheduled::Internal(model) if model.as_ref() == find => {
+                    Scheduled::Internal(model, _) if model.as_ref() == find => {
                         occurences.push(i);
                     }
                     _ => {}
@@ -396,16 +1603,40 @@ impl<'s> Scheduler<'s> {
             }
         }
 
-        if let Some(bounded) = bounded {
-            for (time, values) in self.scheduled.iter_mut() {
-                if !bounded.includes(time) {
-                    break;
-                }
-                remove_model(values, &model);
-            }
-        } else {
-            for values in self.scheduled.values_mut() {
+        if bounded
+            .as_ref()
+            .map(|it| it.includes(&self.time))
+            .unwrap_or(true)
+        {
+            self.pending
+                .retain(|it| !matches!(it, Scheduled::Internal(it, _) if *it == model));
+        }
+
+        let times = match self.update_times.get_mut(&model) {
+            Some(it) => it,
+            None => return,
+        };
+        let cancelled: Vec<Time> = match &bounded {
+            Some(bounded) => times
+                .iter()
+                .filter(|it| bounded.includes(it))
+                .copied()
+                .collect(),
+            None => times.iter().copied().collect(),
+        };
+        for time in &cancelled {
+            times.remove(time);
+        }
+        if times.is_empty() {
+            self.update_times.remove(&model);
+        }
+
+        for time in cancelled {
+            if let Some(values) = self.scheduled.get_mut(&time) {
                 remove_model(values, &model);
+                if values.is_empty() {
+                    self.scheduled.remove(&time);
+                }
             }
         }
     }
@@ -416,7 +1647,20 @@ impl<'s> Scheduler<'s> {
         time: impl Into<Time>,
         model: impl ToCowStr<'s>,
     ) -> Result<(), SchedulerError> {
-        self.schedule(time.into(), Scheduled::Internal(model.to_cow_str()))
+        self.schedule(time.into(), Scheduled::Internal(model.to_cow_str(), None))
+    }
+
+    #[inline]
+    pub fn schedule_update_with_token(
+        &mut self,
+        time: impl Into<Time>,
+        model: impl ToCowStr<'s>,
+        token: u64,
+    ) -> Result<(), SchedulerError> {
+        self.schedule(
+            time.into(),
+            Scheduled::Internal(model.to_cow_str(), Some(token)),
+        )
     }
 
     #[inline]
@@ -435,7 +1679,27 @@ impl<'s> Scheduler<'s> {
         )
     }
 
+    /// Returns all pending entries ordered by time.
+    ///
+    /// Unprocessed entries for current time come first and can be split into
+    /// multiple items with the same time.
+    pub fn timeline(&self) -> impl Iterator<Item = (Time, &[Scheduled<'s>])> + use<'_, 's> {
+        let (front, back) = self.pending.as_slices();
+        [front, back]
+            .into_iter()
+            .filter(|it| !it.is_empty())
+            .map(|it| (self.time, it))
+            .chain(
+                self.scheduled
+                    .iter()
+                    .map(|(time, entries)| (*time, entries.as_slice())),
+            )
+    }
+
     pub fn get_next_time(&self) -> Option<Time> {
+        if !self.pending.is_empty() {
+            return Some(self.time);
+        }
         self.scheduled.first_key_value().map(|(it, _)| it.clone())
     }
 }
@@ -444,7 +1708,11 @@ impl<'s> Iterator for Scheduler<'s> {
     type Item = Vec<Scheduled<'s>>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if !self.pending.is_empty() {
+            return Some(self.pending.drain(..).collect());
+        }
         let (time, result) = self.scheduled.pop_first()?;
+        self.unindex_updates(&time, &result);
         self.time = time;
         Some(result)
     }
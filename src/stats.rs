@@ -0,0 +1,197 @@
+use std::ops::Range;
+
+/// Accumulates count, mean, variance and extremes of a stream of values
+/// without storing them.
+///
+/// Variance is computed with Welford's algorithm, so it stays accurate for
+/// long streams.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnlineStats {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl OnlineStats {
+    pub fn new() -> Self {
+        OnlineStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Combines statistics of two streams as if all values were pushed into
+    /// one.
+    pub fn merge(&mut self, other: &OnlineStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 += other.m2 + delta * delta * (self.count * other.count) as f64 / count as f64;
+        self.count = count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Returns population variance of pushed values.
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.m2 / self.count as f64)
+    }
+
+    /// Returns unbiased sample variance of pushed values.
+    pub fn sample_variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.m2 / (self.count - 1) as f64)
+    }
+
+    /// Returns population standard deviation of pushed values.
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+impl Default for OnlineStats {
+    fn default() -> Self {
+        OnlineStats::new()
+    }
+}
+
+impl Extend<f64> for OnlineStats {
+    fn extend<T: IntoIterator<Item = f64>>(&mut self, iter: T) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl FromIterator<f64> for OnlineStats {
+    fn from_iter<T: IntoIterator<Item = f64>>(iter: T) -> Self {
+        let mut result = OnlineStats::new();
+        result.extend(iter);
+        result
+    }
+}
+
+/// Counts values falling into equally wide bins of a range.
+///
+/// Values outside the range are counted separately as underflow and
+/// overflow.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Histogram {
+    start: f64,
+    end: f64,
+    counts: Vec<u64>,
+    underflow: u64,
+    overflow: u64,
+}
+
+impl Histogram {
+    /// Creates a histogram with `bins` bins spanning `range`.
+    pub fn new(range: Range<f64>, bins: usize) -> Self {
+        if bins == 0 {
+            panic!("histogram must have at least one bin")
+        }
+        if !range.start.is_finite() || !range.end.is_finite() || range.start >= range.end {
+            panic!("histogram range must be finite and non-empty")
+        }
+        Histogram {
+            start: range.start,
+            end: range.end,
+            counts: vec![0; bins],
+            underflow: 0,
+            overflow: 0,
+        }
+    }
+
+    /// Counts `value` into its bin; NaN values are ignored.
+    pub fn push(&mut self, value: f64) {
+        if value < self.start {
+            self.underflow += 1;
+        } else if value >= self.end {
+            self.overflow += 1;
+        } else if !value.is_nan() {
+            let bin = ((value - self.start) / self.bin_width()) as usize;
+            // rounding can place values right below the end past the last bin
+            let last = self.counts.len() - 1;
+            self.counts[bin.min(last)] += 1;
+        }
+    }
+
+    pub fn bin_width(&self) -> f64 {
+        (self.end - self.start) / self.counts.len() as f64
+    }
+
+    /// Returns counts of values in each bin.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Iterates over ranges of bins along with their counts.
+    pub fn bins(&self) -> impl Iterator<Item = (Range<f64>, u64)> + '_ {
+        let width = self.bin_width();
+        self.counts.iter().enumerate().map(move |(i, count)| {
+            let start = self.start + width * i as f64;
+            (start..start + width, *count)
+        })
+    }
+
+    /// Number of values smaller than the start of the range.
+    pub fn underflow(&self) -> u64 {
+        self.underflow
+    }
+
+    /// Number of values greater or equal to the end of the range.
+    pub fn overflow(&self) -> u64 {
+        self.overflow
+    }
+
+    /// Total number of pushed values, including ones outside the range.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum::<u64>() + self.underflow + self.overflow
+    }
+}
+
+impl Extend<f64> for Histogram {
+    fn extend<T: IntoIterator<Item = f64>>(&mut self, iter: T) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
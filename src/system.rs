@@ -1,23 +1,42 @@
 use std::{
     any::TypeId,
-    collections::HashMap,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
     ops::{Deref, DerefMut},
+    rc::Rc,
 };
 
 use crate::{
     error::ModelStoreError,
     error::ValidationError,
-    model::{Model, ModelImpl},
-    routes::{ConnectorPath, Route},
-    util::{CowStr, ToCowStr},
+    event::{ErasedEvent, Message},
+    model::Model,
+    routes::{ConnectorPath, InputConnectorInfo, OutputConnectorInfo, Route, RouteMap},
+    util::{closest_match, CowStr, ToCowStr},
 };
 
 pub(crate) type IdStore<'s, Value> = HashMap<CowStr<'s>, Value>;
 pub struct SystemModel<'s> {
     pub(crate) models: ModelStore<'s>,
     pub(crate) routes: HashMap<ConnectorPath<'s>, ConnectorPath<'s>>,
+    /// Conversions of mapped routes, keyed by route output.
+    pub(crate) route_maps: HashMap<ConnectorPath<'s>, Rc<RouteMap>>,
+    /// Conversions applied to events delivered to inputs of a different
+    /// type, keyed by source and target type.
+    pub(crate) coercions: HashMap<(TypeId, TypeId), RouteMap>,
     pub(crate) validated: bool,
     pub(crate) route_cache: IdStore<'s, AdjacentModels<'s>>,
+    /// Priorities of input connectors that don't have the default one.
+    pub(crate) input_priorities: HashMap<ConnectorPath<'s>, i32>,
+    /// Input and output connector names of each model at validation.
+    #[cfg(debug_assertions)]
+    pub(crate) connector_lists: IdStore<'s, (Vec<&'static str>, Vec<String>)>,
+    /// Outputs of routes each model is an end of.
+    model_routes: IdStore<'s, HashSet<ConnectorPath<'s>>>,
+    /// Outputs of routes pushed since last validation.
+    dirty_routes: HashSet<ConnectorPath<'s>>,
+    /// Models pushed or disconnected from a route since last validation.
+    dirty_models: HashSet<CowStr<'s>>,
 }
 
 impl<'s> Default for SystemModel<'s> {
@@ -31,122 +50,572 @@ impl<'s> SystemModel<'s> {
         Self {
             models: ModelStore::new(),
             routes: HashMap::new(),
+            route_maps: HashMap::new(),
+            coercions: HashMap::new(),
             validated: false,
             route_cache: IdStore::new(),
+            input_priorities: HashMap::new(),
+            #[cfg(debug_assertions)]
+            connector_lists: IdStore::new(),
+            model_routes: IdStore::new(),
+            dirty_routes: HashSet::new(),
+            dirty_models: HashSet::new(),
         }
     }
 
+    /// Creates a system with space reserved for provided number of models and
+    /// routes.
+    pub fn with_capacity(models: usize, routes: usize) -> Self {
+        Self {
+            models: ModelStore::with_capacity(models),
+            routes: HashMap::with_capacity(routes),
+            route_maps: HashMap::new(),
+            coercions: HashMap::new(),
+            validated: false,
+            route_cache: IdStore::with_capacity(models),
+            input_priorities: HashMap::new(),
+            #[cfg(debug_assertions)]
+            connector_lists: IdStore::new(),
+            model_routes: IdStore::new(),
+            dirty_routes: HashSet::new(),
+            dirty_models: HashSet::new(),
+        }
+    }
+
+    /// Adds a model to the system.
+    ///
+    /// Models are initialized in the order they were first added in, unless
+    /// [SimulationBuilder::topological_init](crate::simulation::SimulationBuilder::topological_init)
+    /// is enabled.
     pub fn push_model(&mut self, id: impl ToString, model: impl Model<'s> + 'static) {
-        self.models.insert(id, model);
+        self.push_boxed_model(id, Box::new(model));
+    }
+
+    /// Adds a type-erased model to the system, e.g. one constructed from
+    /// configuration by a model registry.
+    pub fn push_boxed_model(&mut self, id: impl ToString, model: Box<dyn Model<'s>>) {
+        let id = id.to_string();
+        self.dirty_models.insert(CowStr::Owned(id.clone()));
+        self.models.insert_boxed(id, model);
         self.validated = false;
     }
 
+    /// Inserts all models of the same type from provided iterator.
+    pub fn push_models<I, Id, M>(&mut self, models: I)
+    where
+        I: IntoIterator<Item = (Id, M)>,
+        Id: ToString,
+        M: Model<'s> + 'static,
+    {
+        let models = models.into_iter();
+        self.models.reserve(models.size_hint().0);
+        for (id, model) in models {
+            self.push_model(id, model);
+        }
+    }
+
+    /// Connects an output connector to an input connector.
+    ///
+    /// Models can be connected to themselves; events sent through such routes
+    /// are delivered after the handler that emitted them returns.
     pub fn push_route(&mut self, from: ConnectorPath<'s>, to: ConnectorPath<'s>) {
-        self.routes.insert(from, to);
+        let unmapped = self.route_maps.remove(&from).is_none();
+        if unmapped && self.routes.get(&from) == Some(&to) {
+            return;
+        }
+        self.insert_route(from, to);
+    }
+
+    /// Connects an output connector to an input connector of a different
+    /// type, converting events with provided `map` function.
+    ///
+    /// Validation checks that `A` matches the output type and `B` matches the
+    /// input type.
+    pub fn push_route_mapped<A: Message, B: Message>(
+        &mut self,
+        from: ConnectorPath<'s>,
+        to: ConnectorPath<'s>,
+        map: impl Fn(A) -> B + 'static,
+    ) {
+        self.route_maps
+            .insert(from.clone(), Rc::new(RouteMap::new(map)));
+        self.insert_route(from, to);
+    }
+
+    /// Allows events of type `S` to be delivered to input connectors
+    /// receiving `T`, converting them with provided `coerce` function.
+    ///
+    /// Unlike [SystemModel::push_route_mapped], coercions apply to all routes
+    /// of the system. This allows a single input receiving a trait object
+    /// (e.g. `Box<dyn Trait>`) to accept events of any registered type
+    /// implementing the trait:
+    ///
+    /// ```ignore
+    /// system.push_coercion(|it: Circle| Box::new(it) as Box<dyn Shape>);
+    /// ```
+    pub fn push_coercion<S: Message, T: Message>(&mut self, coerce: impl Fn(S) -> T + 'static) {
+        self.coercions.insert(
+            (TypeId::of::<S>(), TypeId::of::<T>()),
+            RouteMap::new(coerce),
+        );
+    }
+
+    /// Returns `true` if events of type `source` can be delivered to input
+    /// connectors receiving `target`.
+    pub fn can_coerce(&self, source: TypeId, target: TypeId) -> bool {
+        source == target || self.coercions.contains_key(&(source, target))
+    }
+
+    /// Converts `event` to `target` type if it's of a different type and a
+    /// coercion between them was registered.
+    pub(crate) fn coerce(&self, event: ErasedEvent, target: TypeId) -> ErasedEvent {
+        match self.coercions.get(&(event.type_id, target)) {
+            Some(coercion) => coercion.apply(event),
+            None => event,
+        }
+    }
+
+    fn insert_route(&mut self, from: ConnectorPath<'s>, to: ConnectorPath<'s>) {
+        if let Some(previous) = self.routes.insert(from.clone(), to.clone()) {
+            if previous.model != from.model {
+                if let Some(routes) = self.model_routes.get_mut(&previous.model) {
+                    routes.remove(&from);
+                }
+            }
+            self.dirty_models.insert(previous.model);
+        }
+        for model in [&from.model, &to.model] {
+            self.model_routes
+                .entry(model.clone())
+                .or_default()
+                .insert(from.clone());
+        }
+        self.dirty_routes.insert(from);
         self.validated = false;
     }
 
+    /// Inserts all routes from provided iterator.
+    pub fn push_routes<I>(&mut self, routes: I)
+    where
+        I: IntoIterator<Item = (ConnectorPath<'s>, ConnectorPath<'s>)>,
+    {
+        let routes = routes.into_iter();
+        self.routes.reserve(routes.size_hint().0);
+        for (from, to) in routes {
+            self.push_route(from, to);
+        }
+    }
+
     pub fn routes<'a>(&'a self) -> impl Iterator<Item = Route<'s>> + 'a {
         self.routes.iter().map(Route::from)
     }
 
+    /// Returns routes going out of model with provided id.
+    pub fn routes_from<'a>(
+        &'a self,
+        model: impl AsRef<str> + 'a,
+    ) -> impl Iterator<Item = Route<'s>> + 'a {
+        self.routes().filter(move |it| it.starts_in_model(&model))
+    }
+
+    /// Returns routes coming into model with provided id.
+    pub fn routes_to<'a>(
+        &'a self,
+        model: impl AsRef<str> + 'a,
+    ) -> impl Iterator<Item = Route<'s>> + 'a {
+        self.routes().filter(move |it| it.ends_in_model(&model))
+    }
+
+    /// Returns routes adjacent to model with provided id.
+    ///
+    /// Uses connections cached by [SystemModel::validate] if the system is
+    /// validated and collects them from pushed routes otherwise.
+    pub fn adjacent_models(&self, model: impl AsRef<str>) -> AdjacentModels<'s> {
+        let model = model.as_ref();
+        if self.validated {
+            if let Some(cached) = self.route_cache.get(model) {
+                return cached.clone();
+            }
+        }
+
+        let outputs: Vec<Route<'s>> = self.routes_from(model).collect();
+        let maps = outputs
+            .iter()
+            .filter_map(|route| {
+                let from = route.from_connection()?;
+                let map = self.route_maps.get(&from)?;
+                Some((from.connector, map.clone()))
+            })
+            .collect();
+        AdjacentModels {
+            inputs: self.routes_to(model).collect(),
+            outputs,
+            maps,
+        }
+    }
+
+    /// Returns connectors of all models in the order they were added in.
+    pub fn describe(&self) -> Vec<ModelDescription> {
+        self.models
+            .keys()
+            .filter_map(|id| {
+                let model = self.models.get(id)?;
+                Some(ModelDescription {
+                    id: id.to_string(),
+                    inputs: model.input_connector_infos(),
+                    outputs: model.output_connectors(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns `true` if routes between models don't form any cycles.
+    pub fn is_acyclic(&self) -> bool {
+        self.find_cycle().is_none()
+    }
+
+    /// Validates the system like [SystemModel::validate] and additionally
+    /// rejects systems with cyclic routes.
+    pub fn validate_acyclic(&mut self) -> Result<(), ValidationError> {
+        self.validate()?;
+        match self.find_cycle() {
+            Some(models) => Err(ValidationError::CycleDetected { models }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns model ids ordered so that every model comes after all models
+    /// routing into it.
+    ///
+    /// Models that don't depend on each other keep their insertion order.
+    pub fn topological_order(&self) -> Result<Vec<CowStr<'s>>, ValidationError> {
+        let ids: Vec<&CowStr<'s>> = self.models.keys().collect();
+        let index: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.as_ref(), i))
+            .collect();
+
+        let mut targets: Vec<Vec<usize>> = vec![vec![]; ids.len()];
+        for (from, to) in self.routes.iter() {
+            if let (Some(&from), Some(&to)) =
+                (index.get(from.model.as_ref()), index.get(to.model.as_ref()))
+            {
+                targets[from].push(to);
+            }
+        }
+
+        let mut in_degree = vec![0usize; ids.len()];
+        for model_targets in targets.iter_mut() {
+            model_targets.sort_unstable();
+            model_targets.dedup();
+            for &target in model_targets.iter() {
+                in_degree[target] += 1;
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = in_degree
+            .iter()
+            .enumerate()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(i, _)| Reverse(i))
+            .collect();
+        let mut result = Vec::with_capacity(ids.len());
+        while let Some(Reverse(current)) = ready.pop() {
+            result.push(ids[current].clone());
+            for &target in targets[current].iter() {
+                in_degree[target] -= 1;
+                if in_degree[target] == 0 {
+                    ready.push(Reverse(target));
+                }
+            }
+        }
+
+        if result.len() != ids.len() {
+            return Err(ValidationError::CycleDetected {
+                models: self.find_cycle().unwrap_or_default(),
+            });
+        }
+        Ok(result)
+    }
+
+    /// Returns ids of models forming a cycle, with the first model repeated at
+    /// the end, or `None` if the route graph is acyclic.
+    fn find_cycle(&self) -> Option<Vec<String>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Visit {
+            New,
+            Active,
+            Done,
+        }
+
+        fn visit<'a>(
+            model: &'a str,
+            targets: &HashMap<&'a str, Vec<&'a str>>,
+            state: &mut HashMap<&'a str, Visit>,
+            path: &mut Vec<&'a str>,
+        ) -> Option<Vec<String>> {
+            state.insert(model, Visit::Active);
+            path.push(model);
+            for target in targets.get(model).into_iter().flatten() {
+                match state.get(target).copied().unwrap_or(Visit::New) {
+                    Visit::Active => {
+                        let start = path.iter().position(|it| it == target).unwrap();
+                        let mut cycle: Vec<String> =
+                            path[start..].iter().map(|it| it.to_string()).collect();
+                        cycle.push(target.to_string());
+                        return Some(cycle);
+                    }
+                    Visit::New => {
+                        if let Some(cycle) = visit(target, targets, state, path) {
+                            return Some(cycle);
+                        }
+                    }
+                    Visit::Done => {}
+                }
+            }
+            path.pop();
+            state.insert(model, Visit::Done);
+            None
+        }
+
+        let mut targets: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (from, to) in self.routes.iter() {
+            targets
+                .entry(from.model.as_ref())
+                .or_default()
+                .push(to.model.as_ref());
+        }
+
+        let mut state = HashMap::new();
+        let mut path = vec![];
+        for model in self.models.ids() {
+            if !state.contains_key(model) {
+                if let Some(cycle) = visit(model, &targets, &mut state, &mut path) {
+                    return Some(cycle);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if the system was validated since it was last modified.
+    pub fn is_validated(&self) -> bool {
+        self.validated
+    }
+
+    /// Validates routes and models changed since last validation and caches
+    /// their connections.
+    ///
+    /// Unchanged parts of the system aren't checked again, so interleaving
+    /// validation with edits stays cheap for large systems.
     pub fn validate(&mut self) -> Result<(), ValidationError> {
         if self.validated == true {
             return Ok(());
         }
 
-        for (a, b) in self.routes.iter() {
-            let model_a = self.models.borrow(a.model.clone())?.ok_or_else(|| {
-                ValidationError::MissingModel {
+        let mut checked: HashSet<ConnectorPath<'s>> = self.dirty_routes.clone();
+        for model in &self.dirty_models {
+            if let Some(routes) = self.model_routes.get(model) {
+                checked.extend(routes.iter().cloned());
+            }
+        }
+
+        let mut changed = self.dirty_models.clone();
+        for from in checked {
+            let to = match self.routes.get(&from) {
+                Some(it) => it.clone(),
+                None => continue,
+            };
+            self.validate_route(&from, &to)?;
+            changed.insert(from.model);
+            changed.insert(to.model);
+        }
+
+        for model in &changed {
+            self.cache_connections(model);
+        }
+        self.dirty_routes.clear();
+        self.dirty_models.clear();
+        self.validated = true;
+
+        Ok(())
+    }
+
+    /// Validates all routes and models of the system, regardless of whether
+    /// they changed since last validation.
+    pub fn validate_full(&mut self) -> Result<(), ValidationError> {
+        self.dirty_routes.extend(self.routes.keys().cloned());
+        self.dirty_models.extend(self.models.keys().cloned());
+        self.validated = false;
+        self.validate()
+    }
+
+    fn validate_route(
+        &mut self,
+        a: &ConnectorPath<'s>,
+        b: &ConnectorPath<'s>,
+    ) -> Result<(), ValidationError> {
+        let model_a =
+            self.models
+                .borrow(a.model.clone())?
+                .ok_or_else(|| ValidationError::MissingModel {
                     id: a.model.to_string(),
-                }
-            })?;
+                    suggestion: closest_match(&a.model, self.models.ids()),
+                })?;
 
-            let model_b = self.models.borrow(b.model.clone())?.ok_or_else(|| {
+        // self routes must borrow the model only once
+        let borrowed_b;
+        let model_b: &dyn Model<'s> = if a.model == b.model {
+            &*model_a
+        } else {
+            borrowed_b = self.models.borrow(b.model.clone())?.ok_or_else(|| {
                 ValidationError::MissingModel {
                     id: b.model.to_string(),
+                    suggestion: closest_match(&b.model, self.models.ids()),
                 }
             })?;
+            &*borrowed_b
+        };
 
-            let output_type = model_a
-                .output_type_id(a.connector.to_string())
-                .ok_or_else(|| ValidationError::MissingConnector {
-                    model: a.model.to_string(),
-                    id: a.connector.to_string(),
-                })?;
-
-            let input_type = model_b.input_type_id(b.connector.as_ref()).ok_or_else(|| {
-                ValidationError::MissingConnector {
-                    model: b.model.to_string(),
-                    id: b.connector.to_string(),
-                }
+        let output = model_a
+            .output_connectors()
+            .into_iter()
+            .find(|it| it.id() == a.connector.as_ref())
+            .ok_or_else(|| ValidationError::MissingConnector {
+                model: a.model.to_string(),
+                id: a.connector.to_string(),
+                suggestion: closest_match(
+                    &a.connector,
+                    model_a.output_connectors().iter().map(|it| it.id()),
+                ),
             })?;
+        let output_type = output.type_id();
+
+        let input = model_b
+            .input_connector_infos()
+            .into_iter()
+            .find(|it| it.id() == b.connector.as_ref())
+            .ok_or_else(|| ValidationError::MissingConnector {
+                model: b.model.to_string(),
+                id: b.connector.to_string(),
+                suggestion: closest_match(&b.connector, model_b.input_connectors()),
+            })?;
+        let input_type = input.type_id();
 
-            if input_type != output_type {
-                return Err(ValidationError::ConnectionTypeMismatch {
-                    output_model: a.model.to_string(),
-                    output_connector: a.connector.to_string(),
-                    input_model: b.model.to_string(),
-                    input_connector: b.connector.to_string(),
-                });
-            }
+        let types_match = match self.route_maps.get(a) {
+            Some(map) => map.input == output_type && self.can_coerce(map.output, input_type),
+            None => self.can_coerce(output_type, input_type),
+        };
+        if !types_match {
+            return Err(ValidationError::ConnectionTypeMismatch {
+                output_model: a.model.to_string(),
+                output_connector: a.connector.to_string(),
+                output_type: output.type_name(),
+                input_model: b.model.to_string(),
+                input_connector: b.connector.to_string(),
+                input_type: input.type_name(),
+            });
+        }
 
-            let non_matching = (0..model_b.input_connectors().len())
-                .filter_map(|i| model_b.get_input_handler(i).map(|h| (i, h)))
-                .map(|(i, handler)| (i, handler.model_type_id()))
-                .find(|(_, id)| *id != model_b.type_id());
+        let non_matching = (0..model_b.input_connectors().len())
+            .filter_map(|i| model_b.input_model_type_id(i).map(|id| (i, id)))
+            .find(|(_, id)| *id != model_b.type_id());
 
-            if let Some((found_i, _)) = non_matching {
-                return Err(ValidationError::InvalidConnectorModel {
-                    connector: model_b.input_connectors()[found_i],
-                });
-            }
+        if let Some((found_i, _)) = non_matching {
+            return Err(ValidationError::InvalidConnectorModel {
+                connector: model_b.input_connectors()[found_i],
+            });
         }
 
-        self.validated = true;
-
-        self.cache_connections()
+        Ok(())
     }
 
-    fn cache_connections(&mut self) -> Result<(), ValidationError> {
-        self.route_cache.clear();
-
-        for id in self.models.keys() {
-            let mut inputs = vec![];
-            let mut outputs = vec![];
+    #[cfg(debug_assertions)]
+    fn connector_list(model: &dyn Model<'s>) -> (Vec<&'static str>, Vec<String>) {
+        let outputs = model
+            .output_connectors()
+            .into_iter()
+            .map(|it| it.id)
+            .collect();
+        (model.input_connectors(), outputs)
+    }
 
-            let mut used_outputs = vec![];
+    /// Checks that connectors of a model didn't change since validation.
+    ///
+    /// Only done in debug builds as listing connectors allocates.
+    #[cfg(debug_assertions)]
+    pub(crate) fn check_connectors(&self, id: &str) -> Result<(), ValidationError> {
+        let (model, cached) = match (self.models.get(id), self.connector_lists.get(id)) {
+            (Some(model), Some(cached)) => (model, cached),
+            _ => return Ok(()),
+        };
+        if Self::connector_list(model) != *cached {
+            return Err(ValidationError::ConnectorsChanged {
+                model: id.to_string(),
+            });
+        }
+        Ok(())
+    }
 
-            for route in self.routes() {
-                if route.ends_in_model(&id) {
-                    inputs.push(route.clone());
-                } else if route.starts_in_model(&id) {
-                    let from = route.from_connection().unwrap().connector;
-                    if used_outputs.contains(&from) {
-                        return Err(ValidationError::RepeatedOutput {
-                            connector: from.to_string(),
-                        });
-                    } else {
-                        used_outputs.push(from);
-                    }
-                    outputs.push(route.clone());
+    /// Caches connections of model with provided id.
+    fn cache_connections(&mut self, id: &CowStr<'s>) {
+        self.input_priorities.retain(|path, _| path.model != *id);
+        if let Some(model) = self.models.get(id) {
+            #[cfg(debug_assertions)]
+            self.connector_lists
+                .insert(id.clone(), Self::connector_list(model));
+            for info in model.input_connector_infos() {
+                if info.priority() != 0 {
+                    self.input_priorities
+                        .insert(ConnectorPath::new(id, info.id()), info.priority());
                 }
             }
-            self.route_cache
-                .insert(id.clone(), AdjacentModels { inputs, outputs });
         }
 
-        Ok(())
+        let mut adjacent = AdjacentModels::default();
+        for from in self.model_routes.get(id).into_iter().flatten() {
+            let route = match self.routes.get(from) {
+                Some(to) => Route::new(from.clone(), to.clone()),
+                None => continue,
+            };
+            if route.ends_in_model(id) {
+                adjacent.inputs.push(route.clone());
+            }
+            // not exclusive with above as self routes are both
+            if route.starts_in_model(id) {
+                if let Some(map) = self.route_maps.get(from) {
+                    adjacent.maps.insert(from.connector.clone(), map.clone());
+                }
+                adjacent.outputs.push(route);
+            }
+        }
+        self.route_cache.insert(id.clone(), adjacent);
     }
 }
 
+/// Connectors of a model returned by [SystemModel::describe].
+#[derive(Debug, Clone)]
+pub struct ModelDescription {
+    pub id: String,
+    pub inputs: Vec<InputConnectorInfo>,
+    pub outputs: Vec<OutputConnectorInfo>,
+}
+
 #[derive(Clone)]
 pub struct AdjacentModels<'s> {
     pub inputs: Vec<Route<'s>>,
     pub outputs: Vec<Route<'s>>,
+    /// Conversions of mapped output routes, keyed by output connector.
+    pub maps: HashMap<CowStr<'s>, Rc<RouteMap>>,
 }
 
 impl<'s> AdjacentModels<'s> {
+    /// Returns the conversion applied to events pushed to `output`.
+    pub fn route_map(&self, output: &str) -> Option<&RouteMap> {
+        self.maps.get(output).map(|it| &**it)
+    }
+
     pub fn adjacent_input(&self, output: CowStr<'s>) -> Option<ConnectorPath<'s>> {
         self.outputs.iter().find_map(|route| {
             if route.from_connection().unwrap().connector == output {
@@ -163,6 +632,7 @@ impl<'s> Default for AdjacentModels<'s> {
         AdjacentModels {
             inputs: vec![],
             outputs: vec![],
+            maps: HashMap::new(),
         }
     }
 }
@@ -174,9 +644,9 @@ pub struct ModelSlot<'s> {
 }
 
 impl<'s> ModelSlot<'s> {
-    pub(crate) fn new(value: impl Model<'s> + 'static) -> Self {
+    pub(crate) fn new(value: Box<dyn Model<'s>>) -> Self {
         Self {
-            value: Box::new(value),
+            value,
             taken: false,
         }
     }
@@ -208,8 +678,14 @@ impl<'s> ModelSlot<'s> {
     }
 }
 
+/// Stores models by their id.
+///
+/// Iteration and index based access follow model insertion order, so model
+/// initialization order is deterministic.
 pub(crate) struct ModelStore<'s> {
     data: HashMap<CowStr<'s>, ModelSlot<'s>>,
+    /// Model ids in insertion order.
+    order: Vec<CowStr<'s>>,
 }
 
 #[allow(unused)]
@@ -217,19 +693,41 @@ impl<'s> ModelStore<'s> {
     pub fn new() -> Self {
         Self {
             data: HashMap::new(),
+            order: Vec::new(),
         }
     }
 
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: HashMap::with_capacity(capacity),
+            order: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.order.reserve(additional);
+    }
+
+    /// Inserts a model, replacing the one with the same id while keeping its
+    /// position.
     pub fn insert(&mut self, id: impl ToString, model: impl Model<'s> + 'static) {
-        self.data
-            .insert(CowStr::Owned(id.to_string()), ModelSlot::new(model));
+        self.insert_boxed(id, Box::new(model));
     }
 
-    pub fn get(&mut self, id: impl AsRef<str>) -> Option<&dyn Model<'s>> {
-        let slot = match self.data.get_mut(id.as_ref()) {
-            Some(it) => it,
-            None => return None,
-        };
+    pub fn insert_boxed(&mut self, id: impl ToString, model: Box<dyn Model<'s>>) {
+        let id = CowStr::Owned(id.to_string());
+        if self
+            .data
+            .insert(id.clone(), ModelSlot::new(model))
+            .is_none()
+        {
+            self.order.push(id);
+        }
+    }
+
+    pub fn get(&self, id: impl AsRef<str>) -> Option<&dyn Model<'s>> {
+        let slot = self.data.get(id.as_ref())?;
         if !slot.taken {
             Some(unsafe { &*slot.data_ptr() })
         } else {
@@ -237,12 +735,60 @@ impl<'s> ModelStore<'s> {
         }
     }
 
-    pub fn get_i(&mut self, index: usize) -> Option<&dyn Model<'s>> {
-        let name = match self.data.keys().nth(index) {
-            Some(it) => it,
-            None => return None,
+    pub fn get_mut(&mut self, id: impl AsRef<str>) -> Option<&mut dyn Model<'s>> {
+        let slot = self.data.get_mut(id.as_ref())?;
+        if !slot.taken {
+            Some(unsafe { &mut *slot.data_ptr_mut() })
+        } else {
+            None
         }
-        .clone();
+    }
+
+    /// Returns a model with provided id if it's of type `M` and isn't borrowed.
+    pub fn get_typed<M: Model<'s> + 'static>(&self, id: impl AsRef<str>) -> Option<&M> {
+        self.get(id)?.as_any().downcast_ref()
+    }
+
+    /// Mutable variant of [ModelStore::get_typed].
+    pub fn get_typed_mut<M: Model<'s> + 'static>(&mut self, id: impl AsRef<str>) -> Option<&mut M> {
+        self.get_mut(id)?.as_any_mut().downcast_mut()
+    }
+
+    /// Returns two distinct models with provided ids if they're of types `A`
+    /// and `B` and aren't borrowed.
+    ///
+    /// Panics if ids are the same.
+    pub fn get_two_typed_mut<A: Model<'s> + 'static, B: Model<'s> + 'static>(
+        &mut self,
+        id_a: &str,
+        id_b: &str,
+    ) -> (Option<&mut A>, Option<&mut B>) {
+        let [a, b] = self.data.get_disjoint_mut([id_a, id_b]);
+        let a = a
+            .filter(|slot| !slot.taken)
+            .and_then(|slot| slot.value.as_any_mut().downcast_mut());
+        let b = b
+            .filter(|slot| !slot.taken)
+            .and_then(|slot| slot.value.as_any_mut().downcast_mut());
+        (a, b)
+    }
+
+    /// Iterates over model ids in insertion order.
+    pub fn ids<'a>(&'a self) -> impl Iterator<Item = &'a str> + use<'a, 's> {
+        self.order.iter().map(|it| it.as_ref())
+    }
+
+    /// Iterates over all models of type `M` which aren't borrowed.
+    pub fn iter_typed<'a, M: Model<'s> + 'static>(
+        &'a self,
+    ) -> impl Iterator<Item = (&'a str, &'a M)> + use<'a, 's, M> {
+        self.order
+            .iter()
+            .filter_map(|id| Some((id.as_ref(), self.get_typed::<M>(id)?)))
+    }
+
+    pub fn get_i(&self, index: usize) -> Option<&dyn Model<'s>> {
+        let name = self.order.get(index)?.clone();
         self.get(name)
     }
 
@@ -260,7 +806,7 @@ impl<'s> ModelStore<'s> {
     }
 
     pub fn borrow_i(&mut self, index: usize) -> Result<Option<BorrowedModel<'s>>, ModelStoreError> {
-        let name = match self.data.keys().nth(index) {
+        let name = match self.order.get(index) {
             Some(it) => it,
             None => return Ok(None),
         }
@@ -269,7 +815,7 @@ impl<'s> ModelStore<'s> {
     }
 
     pub fn keys(&self) -> impl Iterator<Item = &CowStr<'s>> + '_ {
-        self.data.keys()
+        self.order.iter()
     }
 
     pub fn iter(&mut self) -> ModelStoreIter<'_, 's> {
@@ -289,7 +835,7 @@ impl<'i, 's: 'i> Iterator for ModelStoreIter<'i, 's> {
     type Item = (CowStr<'s>, BorrowedModel<'s>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let key: CowStr<'s> = self.store.data.keys().nth(self.pos)?.clone();
+        let key: CowStr<'s> = self.store.order.get(self.pos)?.clone();
         let value = match self.store.borrow(key.clone()) {
             Ok(value) => value?,
             Err(ModelStoreError::ModelMissing) => {
@@ -303,7 +849,7 @@ impl<'i, 's: 'i> Iterator for ModelStoreIter<'i, 's> {
     }
 }
 
-pub(crate) struct BorrowedModel<'s> {
+pub struct BorrowedModel<'s> {
     owner: *mut ModelSlot<'s>,
     id: CowStr<'s>,
     model: *mut dyn Model<'s>,
@@ -311,7 +857,10 @@ pub(crate) struct BorrowedModel<'s> {
 
 #[allow(unused)]
 impl<'s> BorrowedModel<'s> {
-    pub fn new(owner: *mut ModelSlot<'s>, id: impl ToCowStr<'s>) -> Result<Self, ModelStoreError> {
+    pub(crate) fn new(
+        owner: *mut ModelSlot<'s>,
+        id: impl ToCowStr<'s>,
+    ) -> Result<Self, ModelStoreError> {
         let model = unsafe { (*owner).take()? };
 
         Ok(Self {
@@ -325,22 +874,26 @@ impl<'s> BorrowedModel<'s> {
         &self.id
     }
 
+    /// Casts borrowed model into its concrete type `M`.
+    ///
+    /// Returns `None` if the borrowed model isn't of type `M`.
+    ///
+    /// # Safety
+    ///
+    /// The simulation or system owning the model must outlive the returned
+    /// reference.
     pub unsafe fn cast<M: Model<'s> + 'static>(&self) -> Option<&M> {
-        if (*self.model).type_id() == TypeId::of::<M>() {
-            let val: *mut M = self.model as *mut M;
-            Some(&*val)
-        } else {
-            None
-        }
+        (*self.model).as_any().downcast_ref()
     }
 
+    /// Mutable variant of [BorrowedModel::cast].
+    ///
+    /// # Safety
+    ///
+    /// The simulation or system owning the model must outlive the returned
+    /// reference.
     pub unsafe fn cast_mut<M: Model<'s> + 'static>(&mut self) -> Option<&mut M> {
-        if (*self.model).type_id() == TypeId::of::<M>() {
-            let val: *mut M = self.model as *mut M;
-            Some(&mut *val)
-        } else {
-            None
-        }
+        (*self.model).as_any_mut().downcast_mut()
     }
 }
 
@@ -37,6 +37,66 @@ impl Time {
     pub fn into_repr(self) -> TimeRepr {
         self.0
     }
+
+    /// Creates time from a UNIX timestamp.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the timestamp is out of range for [chrono::NaiveDateTime].
+    #[cfg(feature = "time_chrono")]
+    pub fn from_timestamp(secs: i64, nanos: u32) -> Time {
+        let date_time = chrono::DateTime::from_timestamp(secs, nanos)
+            .expect("timestamp out of range for NaiveDateTime");
+        Self(date_time.naive_utc())
+    }
+
+    /// Returns the number of non-leap seconds since UNIX epoch.
+    #[cfg(feature = "time_chrono")]
+    pub fn timestamp(self) -> i64 {
+        self.0.and_utc().timestamp()
+    }
+
+    /// Returns `false` for float times that can't be ordered meaningfully
+    /// (NaN and negative infinity).
+    pub fn is_valid(&self) -> bool {
+        #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+        let result = !self.0.is_nan() && self.0 != TimeRepr::NEG_INFINITY;
+        #[cfg(feature = "time_chrono")]
+        let result = true;
+        result
+    }
+
+    /// Creates time `secs` seconds after the start of the time scale.
+    ///
+    /// For float time this is the value itself, while for chrono time it's
+    /// relative to UNIX epoch. Unlike literals, this works with all time
+    /// features.
+    #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+    pub fn at_secs(secs: f64) -> Time {
+        Self(secs as TimeRepr)
+    }
+
+    /// Creates time `secs` seconds after the start of the time scale.
+    ///
+    /// For float time this is the value itself, while for chrono time it's
+    /// relative to UNIX epoch. Unlike literals, this works with all time
+    /// features.
+    #[cfg(feature = "time_chrono")]
+    pub fn at_secs(secs: f64) -> Time {
+        Time::from_timestamp(0, 0) + TimeDelta::from_secs_f64(secs)
+    }
+
+    /// Formats time rounded to `precision` decimal places.
+    #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+    pub fn format(&self, precision: usize) -> String {
+        format!("{:.*}", precision, self.0)
+    }
+
+    /// Formats time using a [chrono format string](chrono::format::strftime).
+    #[cfg(feature = "time_chrono")]
+    pub fn format(&self, fmt: &str) -> String {
+        self.0.format(fmt).to_string()
+    }
 }
 
 impl PartialOrd for Time {
@@ -119,6 +179,13 @@ pub struct TimeDelta(DurationRepr);
 const NANOS_IN_SEC: i32 = 1_000_000_000;
 
 impl TimeDelta {
+    #[cfg(feature = "time_f32")]
+    pub const ZERO: Self = Self(0.0f32);
+    #[cfg(feature = "time_f64")]
+    pub const ZERO: Self = Self(0.0f64);
+    #[cfg(feature = "time_chrono")]
+    pub const ZERO: Self = TimeDelta(DurationRepr { secs: 0, nanos: 0 });
+
     #[cfg(feature = "time_f32")]
     pub const MIN: Self = Self(0.0f32);
     #[cfg(feature = "time_f64")]
@@ -162,6 +229,93 @@ impl TimeDelta {
         self.0.into()
     }
 
+    pub fn is_zero(&self) -> bool {
+        *self == TimeDelta::ZERO
+    }
+
+    /// Returns the shorter of two deltas.
+    pub fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+
+    /// Returns the longer of two deltas.
+    pub fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+
+    #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+    pub fn from_secs_f64(secs: f64) -> Self {
+        Self(secs as DurationRepr)
+    }
+    #[cfg(feature = "time_chrono")]
+    pub fn from_secs_f64(secs: f64) -> Self {
+        let whole = secs.floor();
+        let mut nanos = ((secs - whole) * NANOS_IN_SEC as f64).round() as i32;
+        let mut secs = whole as i64;
+        if nanos >= NANOS_IN_SEC {
+            secs += 1;
+            nanos -= NANOS_IN_SEC;
+        }
+        TimeDelta(DurationRepr { secs, nanos })
+    }
+
+    #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+    pub fn as_secs_f64(self) -> f64 {
+        self.0 as f64
+    }
+    #[cfg(feature = "time_chrono")]
+    pub fn as_secs_f64(self) -> f64 {
+        self.0.secs as f64 + self.0.nanos as f64 / NANOS_IN_SEC as f64
+    }
+
+    /// Converts the delta into a [Duration](std::time::Duration), or returns
+    /// `None` if it's negative or too large to be represented by it.
+    #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+    pub fn to_std_duration(&self) -> Option<std::time::Duration> {
+        std::time::Duration::try_from_secs_f64(self.0 as f64).ok()
+    }
+    /// Converts the delta into a [Duration](std::time::Duration), or returns
+    /// `None` if it's negative.
+    #[cfg(feature = "time_chrono")]
+    pub fn to_std_duration(&self) -> Option<std::time::Duration> {
+        self.into_repr().to_std().ok()
+    }
+
+    #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+    pub fn from_std_duration(duration: std::time::Duration) -> Self {
+        Self(duration.as_secs_f64() as DurationRepr)
+    }
+    /// Converts a [Duration](std::time::Duration) into a delta, saturating at
+    /// [TimeDelta::MAX] if it's too large.
+    #[cfg(feature = "time_chrono")]
+    pub fn from_std_duration(duration: std::time::Duration) -> Self {
+        chrono::Duration::from_std(duration)
+            .map(Self::new)
+            .unwrap_or(Self::MAX)
+    }
+
+    #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+    pub fn from_millis(millis: i64) -> Self {
+        Self(millis as DurationRepr / 1000.0)
+    }
+    #[cfg(feature = "time_chrono")]
+    pub fn from_millis(millis: i64) -> Self {
+        Self::new(chrono::Duration::milliseconds(millis))
+    }
+
+    /// Formats duration in seconds, rounded to `precision` decimal places.
+    #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+    pub fn format(&self, precision: usize) -> String {
+        format!("{:.*}", precision, self.0)
+    }
+
+    /// Formats duration in seconds, rounded to `precision` decimal places.
+    #[cfg(feature = "time_chrono")]
+    pub fn format(&self, precision: usize) -> String {
+        let secs = self.0.secs as f64 + self.0.nanos as f64 / 1_000_000_000.0;
+        format!("{:.*}", precision, secs)
+    }
+
     #[cfg(feature = "time_chrono")]
     pub fn seconds(self) -> i64 {
         self.0.secs
@@ -173,6 +327,30 @@ impl TimeDelta {
     }
 }
 
+impl PartialEq for TimeDelta {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for TimeDelta {}
+
+impl PartialOrd for TimeDelta {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimeDelta {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+        let ordering = float_ord::FloatOrd(self.0).cmp(&float_ord::FloatOrd(other.0));
+        #[cfg(feature = "time_chrono")]
+        let ordering = (self.0.secs, self.0.nanos).cmp(&(other.0.secs, other.0.nanos));
+        ordering
+    }
+}
+
 #[cfg(any(feature = "time_f32", feature = "time_f64"))]
 impl From<DurationRepr> for TimeDelta {
     fn from(value: DurationRepr) -> Self {
@@ -199,8 +377,79 @@ impl Into<chrono::Duration> for TimeDelta {
 }
 
 mod op_impl {
+    #[cfg(feature = "time_chrono")]
+    use super::{DurationRepr, NANOS_IN_SEC};
     use super::{Time, TimeDelta};
-    use std::ops::{Add, AddAssign, Sub, SubAssign};
+    use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+    #[cfg(feature = "time_chrono")]
+    impl DurationRepr {
+        fn total_nanos(self) -> i128 {
+            self.secs as i128 * NANOS_IN_SEC as i128 + self.nanos as i128
+        }
+
+        fn from_total_nanos(nanos: i128) -> Self {
+            let secs = nanos.div_euclid(NANOS_IN_SEC as i128);
+            DurationRepr {
+                secs: i64::try_from(secs).expect("TimeDelta overflow"),
+                nanos: nanos.rem_euclid(NANOS_IN_SEC as i128) as i32,
+            }
+        }
+    }
+
+    impl Mul<f64> for TimeDelta {
+        type Output = TimeDelta;
+
+        #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+        fn mul(self, rhs: f64) -> Self::Output {
+            TimeDelta((self.0 as f64 * rhs) as _)
+        }
+        #[cfg(feature = "time_chrono")]
+        fn mul(self, rhs: f64) -> Self::Output {
+            let nanos = (self.0.total_nanos() as f64 * rhs).round() as i128;
+            TimeDelta(DurationRepr::from_total_nanos(nanos))
+        }
+    }
+
+    impl MulAssign<f64> for TimeDelta {
+        fn mul_assign(&mut self, rhs: f64) {
+            *self = *self * rhs;
+        }
+    }
+
+    impl Mul<f32> for TimeDelta {
+        type Output = TimeDelta;
+
+        fn mul(self, rhs: f32) -> Self::Output {
+            self * rhs as f64
+        }
+    }
+
+    impl MulAssign<f32> for TimeDelta {
+        fn mul_assign(&mut self, rhs: f32) {
+            *self = *self * rhs;
+        }
+    }
+
+    impl Mul<u32> for TimeDelta {
+        type Output = TimeDelta;
+
+        #[cfg(any(feature = "time_f32", feature = "time_f64"))]
+        fn mul(self, rhs: u32) -> Self::Output {
+            TimeDelta(self.0 * rhs as super::DurationRepr)
+        }
+        #[cfg(feature = "time_chrono")]
+        fn mul(self, rhs: u32) -> Self::Output {
+            let nanos = self.0.total_nanos() * rhs as i128;
+            TimeDelta(DurationRepr::from_total_nanos(nanos))
+        }
+    }
+
+    impl MulAssign<u32> for TimeDelta {
+        fn mul_assign(&mut self, rhs: u32) {
+            *self = *self * rhs;
+        }
+    }
 
     impl Add for TimeDelta {
         type Output = TimeDelta;
@@ -220,7 +469,7 @@ mod op_impl {
         type Output = TimeDelta;
 
         fn sub(self, rhs: TimeDelta) -> Self::Output {
-            Self::new(self.into_repr() + rhs.into_repr())
+            Self::new(self.into_repr() - rhs.into_repr())
         }
     }
 
@@ -248,7 +497,7 @@ mod op_impl {
         type Output = Time;
 
         fn sub(self, rhs: TimeDelta) -> Self::Output {
-            Self::new(self.0 + rhs.into_repr())
+            Self::new(self.0 - rhs.into_repr())
         }
     }
 
@@ -362,6 +611,7 @@ impl Display for TimeDelta {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimeBounds {
     pub start: Bound<Time>,
@@ -370,7 +620,7 @@ pub struct TimeBounds {
 
 impl TimeBounds {
     pub fn new(start: Time, end: Time) -> Self {
-        if end >= start {
+        if end < start {
             panic!("end time must be greater or equal to start time")
         }
         TimeBounds {
@@ -380,7 +630,7 @@ impl TimeBounds {
     }
 
     pub fn new_inclusive(start: Time, end: Time) -> Self {
-        if end >= start {
+        if end < start {
             panic!("end time must be greater or equal to start time")
         }
         TimeBounds {
@@ -402,6 +652,92 @@ impl TimeBounds {
         }
         return true;
     }
+
+    /// Resolves `trigger` relative to `current` time and returns it if it's
+    /// included in the bounds.
+    pub fn clamp_trigger(&self, trigger: TimeTrigger, current: Time) -> Option<Time> {
+        let time = trigger.to_discrete(current);
+        if self.includes(&time) {
+            Some(time)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if no time is included in the bounds.
+    pub fn is_empty(&self) -> bool {
+        match (self.start, self.end) {
+            (Bound::Included(start), Bound::Included(end)) => start > end,
+            (
+                Bound::Included(start) | Bound::Excluded(start),
+                Bound::Included(end) | Bound::Excluded(end),
+            ) => start >= end,
+            _ => false,
+        }
+    }
+
+    /// Returns bounds including only times included in both bounds, or `None`
+    /// if they're disjoint.
+    pub fn intersect(&self, other: &TimeBounds) -> Option<TimeBounds> {
+        let start = if cmp_start(&self.start, &other.start).is_ge() {
+            self.start
+        } else {
+            other.start
+        };
+        let end = if cmp_end(&self.end, &other.end).is_le() {
+            self.end
+        } else {
+            other.end
+        };
+        let result = TimeBounds { start, end };
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Returns `true` if all times included in `other` are included in these
+    /// bounds.
+    pub fn contains_bounds(&self, other: &TimeBounds) -> bool {
+        other.is_empty()
+            || (cmp_start(&self.start, &other.start).is_le()
+                && cmp_end(&self.end, &other.end).is_ge())
+    }
+}
+
+/// Orders start bounds by the earliest time they include.
+fn cmp_start(a: &Bound<Time>, b: &Bound<Time>) -> std::cmp::Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => std::cmp::Ordering::Equal,
+        (Bound::Unbounded, _) => std::cmp::Ordering::Less,
+        (_, Bound::Unbounded) => std::cmp::Ordering::Greater,
+        (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b))
+            if a != b =>
+        {
+            a.cmp(b)
+        }
+        (Bound::Included(_), Bound::Excluded(_)) => std::cmp::Ordering::Less,
+        (Bound::Excluded(_), Bound::Included(_)) => std::cmp::Ordering::Greater,
+        _ => std::cmp::Ordering::Equal,
+    }
+}
+
+/// Orders end bounds by the latest time they include.
+fn cmp_end(a: &Bound<Time>, b: &Bound<Time>) -> std::cmp::Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => std::cmp::Ordering::Equal,
+        (Bound::Unbounded, _) => std::cmp::Ordering::Greater,
+        (_, Bound::Unbounded) => std::cmp::Ordering::Less,
+        (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b))
+            if a != b =>
+        {
+            a.cmp(b)
+        }
+        (Bound::Included(_), Bound::Excluded(_)) => std::cmp::Ordering::Greater,
+        (Bound::Excluded(_), Bound::Included(_)) => std::cmp::Ordering::Less,
+        _ => std::cmp::Ordering::Equal,
+    }
 }
 
 impl Default for TimeBounds {
@@ -0,0 +1,173 @@
+use std::{borrow::Cow, io::Write};
+
+use crate::{
+    routes::{EventSource, Route},
+    simulation::TimelineEntry,
+    time::Time,
+};
+
+/// Receives every entry processed by a simulation.
+///
+/// Observers are registered with
+/// [Simulation::add_observer](crate::simulation::Simulation::add_observer)
+/// and are notified right before each entry is processed.
+pub trait SimulationObserver<'s> {
+    fn observe(&mut self, time: Time, entry: &TimelineEntry<'s>);
+}
+
+/// Observer that records processed entries so they can be inspected after
+/// the simulation ran.
+///
+/// Recorded entries can be limited to ones involving specific models or
+/// connectors.
+#[derive(Debug, Clone, Default)]
+pub struct TraceRecorder<'s> {
+    records: Vec<(Time, TimelineEntry<'s>)>,
+    models: Vec<String>,
+    connectors: Vec<String>,
+}
+
+impl<'s> TraceRecorder<'s> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only records entries sent from, delivered to or updating model with
+    /// provided id.
+    ///
+    /// Can be called multiple times to record entries of several models.
+    pub fn with_model(mut self, id: impl ToString) -> Self {
+        self.models.push(id.to_string());
+        self
+    }
+
+    /// Only records events sent from or delivered to a connector with
+    /// provided name; internal updates are skipped.
+    ///
+    /// Can be called multiple times to record events of several connectors.
+    pub fn with_connector(mut self, name: impl ToString) -> Self {
+        self.connectors.push(name.to_string());
+        self
+    }
+
+    fn matches(&self, entry: &TimelineEntry<'s>) -> bool {
+        let model_matches = |id: &str| self.models.iter().any(|it| it == id);
+        let connector_matches = |name: &str| self.connectors.iter().any(|it| it == name);
+        match entry {
+            TimelineEntry::Update { model, .. } => {
+                self.connectors.is_empty() && (self.models.is_empty() || model_matches(model))
+            }
+            TimelineEntry::Event { route, .. } => {
+                let from = route.from_connection();
+                (self.models.is_empty()
+                    || model_matches(&route.to.model)
+                    || from.as_ref().is_some_and(|it| model_matches(&it.model)))
+                    && (self.connectors.is_empty()
+                        || connector_matches(&route.to.connector)
+                        || from
+                            .as_ref()
+                            .is_some_and(|it| connector_matches(&it.connector)))
+            }
+        }
+    }
+
+    /// Returns recorded entries in the order they were processed in.
+    pub fn records(&self) -> &[(Time, TimelineEntry<'s>)] {
+        &self.records
+    }
+
+    /// Iterates over recorded events.
+    pub fn events(&self) -> impl Iterator<Item = (Time, &Route<'s>)> {
+        self.records.iter().filter_map(|(time, entry)| match entry {
+            TimelineEntry::Event { route, .. } => Some((*time, route)),
+            _ => None,
+        })
+    }
+
+    /// Returns number of recorded events delivered through `route`.
+    pub fn count_route(&self, route: &Route<'s>) -> usize {
+        self.events().filter(|(_, it)| *it == route).count()
+    }
+
+    /// Returns number of recorded internal updates of model with provided id.
+    pub fn count_updates(&self, model: impl AsRef<str>) -> usize {
+        self.records
+            .iter()
+            .filter(|(_, entry)| {
+                matches!(entry, TimelineEntry::Update { model: id, .. } if id.as_ref() == model.as_ref())
+            })
+            .count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Writes recorded entries to `writer` as CSV with a header row.
+    ///
+    /// Columns are `time, from_model, from_connector, to_model, to_connector,
+    /// event_type, kind`. Source columns are left empty for events without a
+    /// source model and for updates, which have the updated model written to
+    /// `to_model`. `kind` is `event` for events sent by models, `external` or
+    /// `internal` for events without a source model, and `update` for
+    /// updates.
+    pub fn to_csv(&self, mut writer: impl Write) -> std::io::Result<()> {
+        writeln!(
+            writer,
+            "time,from_model,from_connector,to_model,to_connector,event_type,kind"
+        )?;
+        for (time, entry) in &self.records {
+            let fields: [&str; 6] = match entry {
+                TimelineEntry::Update { model, .. } => ["", "", model, "", "", "update"],
+                TimelineEntry::Event { route, type_name } => {
+                    let (from_model, from_connector, kind) = match &route.from {
+                        EventSource::Model(from) => {
+                            (from.model.as_ref(), from.connector.as_ref(), "event")
+                        }
+                        EventSource::External => ("", "", "external"),
+                        EventSource::Internal => ("", "", "internal"),
+                    };
+                    [
+                        from_model,
+                        from_connector,
+                        &route.to.model,
+                        &route.to.connector,
+                        type_name,
+                        kind,
+                    ]
+                }
+            };
+            write!(writer, "{}", csv_field(&time.to_string()))?;
+            for field in fields {
+                write!(writer, ",{}", csv_field(field))?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Quotes `value` if it contains characters with special meaning in CSV.
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+impl<'s> SimulationObserver<'s> for TraceRecorder<'s> {
+    fn observe(&mut self, time: Time, entry: &TimelineEntry<'s>) {
+        if self.matches(entry) {
+            self.records.push((time, entry.clone()));
+        }
+    }
+}
@@ -22,14 +22,192 @@ impl<'s> ToCowStr<'s> for String {
     }
 }
 
+/// Returns the candidate closest to `name` by case-insensitive edit distance,
+/// if it's similar enough to likely be a typo.
+pub(crate) fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let name_lower = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|it| (edit_distance(&name_lower, &it.to_lowercase()), it))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, it)| it.to_string())
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
 #[cfg(feature = "rand")]
 mod rng {
     pub trait SimulationRng: rand_core::RngCore + 'static {}
     impl<T: rand_core::RngCore + 'static> SimulationRng for T {}
 }
-#[cfg(feature = "rand")]
+#[cfg(all(feature = "mini_rng", not(feature = "rand")))]
+mod rng {
+    /// Source of randomness used by the simulation when `rand` feature is
+    /// disabled.
+    pub trait SimulationRng: 'static {
+        fn next_u64(&mut self) -> u64;
+    }
+}
+#[cfg(any(feature = "rand", feature = "mini_rng"))]
 pub use rng::*;
 
+#[cfg(feature = "mini_rng")]
+mod mini_rng {
+    /// Small deterministic xorshift64* PRNG.
+    ///
+    /// It's not suitable for cryptography or statistically demanding
+    /// simulations, but doesn't require any dependencies.
+    #[derive(Debug, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct MiniRng {
+        state: u64,
+    }
+
+    impl MiniRng {
+        pub fn seed_from_u64(seed: u64) -> Self {
+            // splitmix64 step so that similar seeds produce different states and
+            // the state is never zero
+            let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^= z >> 31;
+            MiniRng {
+                state: if z == 0 { 0x9E3779B97F4A7C15 } else { z },
+            }
+        }
+
+        pub fn next_u64(&mut self) -> u64 {
+            self.state ^= self.state >> 12;
+            self.state ^= self.state << 25;
+            self.state ^= self.state >> 27;
+            self.state.wrapping_mul(0x2545F4914F6CDD1D)
+        }
+    }
+
+    impl Default for MiniRng {
+        fn default() -> Self {
+            MiniRng::seed_from_u64(0)
+        }
+    }
+
+    #[cfg(not(feature = "rand"))]
+    impl super::SimulationRng for MiniRng {
+        fn next_u64(&mut self) -> u64 {
+            MiniRng::next_u64(self)
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    impl rand_core::RngCore for MiniRng {
+        fn next_u32(&mut self) -> u32 {
+            (MiniRng::next_u64(self) >> 32) as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            MiniRng::next_u64(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            rand_core::impls::fill_bytes_via_next(self, dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    /// Values that can be sampled from [SimulationRng](super::SimulationRng)
+    /// when `rand` feature is disabled.
+    ///
+    /// Integer ranges are sampled with modulo, so they're slightly biased for
+    /// very large ranges.
+    #[cfg(not(feature = "rand"))]
+    pub trait MiniSample: Sized {
+        fn sample(rng: &mut dyn super::SimulationRng) -> Self;
+        fn sample_range(rng: &mut dyn super::SimulationRng, range: std::ops::Range<Self>) -> Self;
+    }
+
+    #[cfg(not(feature = "rand"))]
+    macro_rules! impl_mini_sample_int {
+        ($($t: ty),*) => {$(
+            impl MiniSample for $t {
+                fn sample(rng: &mut dyn super::SimulationRng) -> Self {
+                    rng.next_u64() as $t
+                }
+
+                fn sample_range(
+                    rng: &mut dyn super::SimulationRng,
+                    range: std::ops::Range<Self>,
+                ) -> Self {
+                    assert!(range.start < range.end, "cannot sample empty range");
+                    let span = range.end.abs_diff(range.start) as u64;
+                    range.start.wrapping_add((rng.next_u64() % span) as $t)
+                }
+            }
+        )*};
+    }
+    #[cfg(not(feature = "rand"))]
+    impl_mini_sample_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+    #[cfg(not(feature = "rand"))]
+    macro_rules! impl_mini_sample_float {
+        ($($t: ty: $bits: literal),*) => {$(
+            impl MiniSample for $t {
+                /// Samples a value in `[0, 1)` range.
+                fn sample(rng: &mut dyn super::SimulationRng) -> Self {
+                    (rng.next_u64() >> (64 - $bits)) as $t / (1u64 << $bits) as $t
+                }
+
+                fn sample_range(
+                    rng: &mut dyn super::SimulationRng,
+                    range: std::ops::Range<Self>,
+                ) -> Self {
+                    range.start + (range.end - range.start) * <$t>::sample(rng)
+                }
+            }
+        )*};
+    }
+    #[cfg(not(feature = "rand"))]
+    impl_mini_sample_float!(f32: 24, f64: 53);
+
+    #[cfg(not(feature = "rand"))]
+    impl MiniSample for bool {
+        fn sample(rng: &mut dyn super::SimulationRng) -> Self {
+            rng.next_u64() >> 63 == 1
+        }
+
+        fn sample_range(rng: &mut dyn super::SimulationRng, range: std::ops::Range<Self>) -> Self {
+            if range.start == range.end {
+                range.start
+            } else {
+                bool::sample(rng)
+            }
+        }
+    }
+}
+#[cfg(feature = "mini_rng")]
+pub use mini_rng::*;
+
 /// Re-exported const TypeId constructor so dependants don't need to enable const_type_id
 /// flag.
 pub const fn const_type_id<T: 'static>() -> std::any::TypeId {
@@ -0,0 +1,123 @@
+#![cfg(feature = "rand")]
+
+use litesim::prelude::*;
+
+/// Re-emits received values after a second.
+struct Delay;
+
+#[litesim_model]
+impl<'s> Model<'s> for Delay {
+    #[output]
+    fn output(&self, value: u32) -> _;
+
+    #[input]
+    fn input(&mut self, value: u32, _: ModelCtx<'s>) -> _ {
+        self.output(value + 1, In(1.0))
+    }
+}
+
+struct Recorder(Vec<(Time, u32)>);
+
+#[litesim_model]
+impl<'s> Model<'s> for Recorder {
+    #[input]
+    fn input(&mut self, value: u32, ctx: ModelCtx<'s>) -> _ {
+        self.0.push((ctx.time, value));
+        Ok(())
+    }
+
+    fn reset(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.0.clear();
+        Ok(())
+    }
+}
+
+/// Wraps a model into a compound model exposing its `input` and `output`
+/// connectors as `in` and `out`.
+fn wrap(inner_id: &'static str, inner: impl Model<'static> + 'static) -> CompoundModel {
+    let mut system = SystemModel::new();
+    system.push_model(inner_id, inner);
+    CompoundModel::builder(system)
+        .input("in", ConnectorPath::new(inner_id, "input"))
+        .output("out", ConnectorPath::new(inner_id, "output"))
+        .build()
+        .unwrap()
+}
+
+fn run(sim: &mut Simulation<'static>) -> Vec<(Time, u32)> {
+    sim.schedule_event(1.0, Event::new(10u32), ConnectorPath::new("outer", "in"))
+        .unwrap();
+    sim.schedule_event(4.0, Event::new(20u32), ConnectorPath::new("outer", "in"))
+        .unwrap();
+    sim.run().unwrap();
+    sim.inspect_model::<Recorder>("recorder").unwrap().0.clone()
+}
+
+#[test]
+fn nested_compound_forwards_through_both_levels() {
+    let mut system = SystemModel::new();
+    system.push_model("inner", wrap("delay", Delay));
+    system.push_model("delay", Delay);
+    system.push_route(
+        ConnectorPath::new("inner", "out"),
+        ConnectorPath::new("delay", "input"),
+    );
+    let outer = CompoundModel::builder(system)
+        .input("in", ConnectorPath::new("inner", "in"))
+        .output("out", ConnectorPath::new("delay", "output"))
+        .build()
+        .unwrap();
+
+    let mut system = SystemModel::new();
+    system.push_model("outer", outer);
+    system.push_model("recorder", Recorder(vec![]));
+    system.push_route(
+        ConnectorPath::new("outer", "out"),
+        ConnectorPath::new("recorder", "input"),
+    );
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+
+    let first = run(&mut sim);
+    assert_eq!(
+        first,
+        vec![(Time::at_secs(3.0), 12), (Time::at_secs(6.0), 22)]
+    );
+
+    sim.reset().unwrap();
+    assert!(sim.inspect_model::<Recorder>("recorder").unwrap().0.is_empty());
+    assert_eq!(run(&mut sim), first);
+}
+
+#[test]
+fn repeated_exposed_names_are_rejected() {
+    let system = || {
+        let mut system = SystemModel::new();
+        system.push_model("a", Delay);
+        system.push_model("b", Delay);
+        system
+    };
+
+    let inputs = CompoundModel::builder(system())
+        .input("in", ConnectorPath::new("a", "input"))
+        .input("in", ConnectorPath::new("b", "input"))
+        .build();
+    assert!(matches!(
+        inputs,
+        Err(ValidationError::RepeatedExposedConnector { connector }) if connector == "in"
+    ));
+
+    let outputs = CompoundModel::builder(system())
+        .output("out", ConnectorPath::new("a", "output"))
+        .output("out", ConnectorPath::new("b", "output"))
+        .build();
+    assert!(matches!(
+        outputs,
+        Err(ValidationError::RepeatedExposedConnector { connector }) if connector == "out"
+    ));
+
+    let distinct = CompoundModel::builder(system())
+        .input("a", ConnectorPath::new("a", "input"))
+        .output("a", ConnectorPath::new("a", "output"))
+        .build();
+    assert!(distinct.is_ok());
+}
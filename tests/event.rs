@@ -0,0 +1,116 @@
+use std::{cell::Cell, rc::Rc};
+
+use litesim::prelude::*;
+
+/// Payload counting how many times it was dropped.
+struct DropFlag(Rc<Cell<usize>>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn erased_flag() -> (ErasedEvent, Rc<Cell<usize>>) {
+    let drops = Rc::new(Cell::new(0));
+    (Event::new(DropFlag(drops.clone())).into(), drops)
+}
+
+#[test]
+fn erased_event_drops_payload() {
+    let (event, drops) = erased_flag();
+    assert_eq!(drops.get(), 0);
+    drop(event);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn restored_event_drops_payload_once() {
+    let (event, drops) = erased_flag();
+    let restored = event.try_restore_type::<DropFlag>().ok().unwrap();
+    assert_eq!(drops.get(), 0);
+    drop(restored);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn failed_restore_keeps_payload() {
+    let (event, drops) = erased_flag();
+    let event = event.try_restore_type::<u32>().err().unwrap();
+    assert_eq!(drops.get(), 0);
+    assert!(event.peek::<DropFlag>().is_some());
+    drop(event);
+    assert_eq!(drops.get(), 1);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn undelivered_event_is_dropped_with_simulation() {
+    let drops = Rc::new(Cell::new(0));
+    let mut system = SystemModel::new();
+    system.push_model("sink", Sink);
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    sim.schedule_event(
+        1.0,
+        Event::new(DropFlag(drops.clone())),
+        ConnectorPath::new("sink", "input"),
+    )
+    .unwrap();
+    assert_eq!(drops.get(), 0);
+    drop(sim);
+    assert_eq!(drops.get(), 1);
+}
+
+struct Sink;
+
+#[litesim_model]
+impl<'s> Model<'s> for Sink {
+    #[input]
+    fn input(&mut self, value: DropFlag, _: ModelCtx<'s>) -> _ {
+        drop(value);
+        Ok(())
+    }
+}
+
+#[test]
+fn cloneable_events_clone_after_erasure() {
+    let event = Event::new_cloneable(vec![1u32, 2]);
+    assert_eq!(event.try_clone().unwrap().into_inner(), vec![1, 2]);
+
+    let erased: ErasedEvent = event.into();
+    let copy = erased.try_clone().unwrap();
+    assert_eq!(copy.peek::<Vec<u32>>(), Some(&vec![1, 2]));
+
+    let restored = erased.try_restore_type::<Vec<u32>>().ok().unwrap();
+    assert!(restored.try_clone().is_some());
+}
+
+#[test]
+fn plain_events_are_not_cloned() {
+    assert!(Event::new(5u32).try_clone().is_none());
+    let erased: ErasedEvent = Event::new(5u32).into();
+    assert!(erased.try_clone().is_none());
+    assert!(Event::new_cloneable(5u32).map(|it| it + 1).try_clone().is_none());
+    assert!(Event::shared(std::sync::Arc::new(5u32))
+        .try_clone()
+        .is_some());
+}
+
+#[test]
+fn cloned_payload_is_dropped_separately() {
+    #[derive(Clone)]
+    struct Counted(Rc<Cell<usize>>);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Rc::new(Cell::new(0));
+    let erased: ErasedEvent = Event::new_cloneable(Counted(drops.clone())).into();
+    let copy = erased.try_clone().unwrap();
+    drop(erased);
+    assert_eq!(drops.get(), 1);
+    drop(copy);
+    assert_eq!(drops.get(), 2);
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "rand")]
+
+use std::any::{Any, TypeId};
+
+use litesim::{
+    model::{ErasedInputHandler, Model},
+    prelude::*,
+    routes::OutputConnectorInfo,
+};
+
+/// Hand-written model reporting a wrong type id.
+struct Misreported(u32);
+
+impl<'s> Model<'s> for Misreported {
+    fn input_connectors(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    fn output_connectors(&self) -> Vec<OutputConnectorInfo> {
+        vec![]
+    }
+
+    fn get_input_handler<'h>(&self, _: usize) -> Option<Box<dyn ErasedInputHandler<'h, 's>>>
+    where
+        's: 'h,
+    {
+        None
+    }
+
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<Counter>()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+struct Counter(u64);
+
+#[litesim_model]
+impl<'s> Model<'s> for Counter {
+    #[input(signal)]
+    fn increment(&mut self, _: ModelCtx<'s>) -> _ {
+        self.0 += 1;
+        Ok(())
+    }
+}
+
+fn simulation() -> Simulation<'static> {
+    let mut system = SystemModel::new();
+    system.push_model("misreported", Misreported(3));
+    system.push_model("counter", Counter(0));
+    Simulation::new(rand::thread_rng(), system, 0.0).unwrap()
+}
+
+#[test]
+fn inspect_checks_concrete_type() {
+    let mut sim = simulation();
+    assert_eq!(sim.inspect_model::<Misreported>("misreported").unwrap().0, 3);
+    assert!(sim.inspect_model::<Counter>("misreported").is_none());
+    assert!(sim.inspect_model_mut::<Counter>("misreported").is_none());
+    assert!(sim.models_of_type::<Counter>().all(|(id, _)| id == "counter"));
+
+    sim.schedule_event(0.0, Event::new(()), ConnectorPath::new("counter", "increment"))
+        .unwrap();
+    sim.run().unwrap();
+    assert_eq!(sim.inspect_model::<Counter>("counter").unwrap().0, 1);
+    assert!(sim.inspect_model::<Misreported>("counter").is_none());
+}
+
+#[test]
+fn inspect_two_mut_returns_distinct_models() {
+    let mut sim = simulation();
+    let (misreported, counter) = sim
+        .inspect_two_mut::<Misreported, Counter>("misreported", "counter")
+        .unwrap();
+    misreported.0 += 1;
+    counter.0 = 10;
+    assert_eq!(sim.inspect_model::<Misreported>("misreported").unwrap().0, 4);
+    assert_eq!(sim.inspect_model::<Counter>("counter").unwrap().0, 10);
+
+    assert!(matches!(
+        sim.inspect_two_mut::<Counter, Counter>("counter", "counter"),
+        Err(SimulationError::ModelStore(ModelStoreError::AliasedBorrow { .. }))
+    ));
+    assert!(matches!(
+        sim.inspect_two_mut::<Counter, Counter>("misreported", "counter"),
+        Err(SimulationError::ModelNotFound { id }) if id == "misreported"
+    ));
+    assert!(matches!(
+        sim.inspect_two_mut::<Counter, Counter>("counter", "missing"),
+        Err(SimulationError::ModelNotFound { id }) if id == "missing"
+    ));
+}
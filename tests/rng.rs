@@ -0,0 +1,48 @@
+#![cfg(feature = "rand")]
+
+use litesim::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Model sampling the global RNG once per second.
+struct Roller(Vec<u64>);
+
+#[litesim_model]
+impl<'s> Model<'s> for Roller {
+    fn init(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        ctx.schedule_update(Now)
+    }
+
+    fn handle_update(&mut self, ctx: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.0.push(ctx.rand());
+        ctx.schedule_update(In(1.0))
+    }
+}
+
+/// Rolls `steps` values with an RNG seeded by `seed`, then `steps` more after
+/// swapping to an RNG seeded by `swapped`.
+fn roll(seed: u64, swapped: u64, steps: usize) -> Vec<u64> {
+    let mut system = SystemModel::new();
+    system.push_model("roller", Roller(vec![]));
+    let mut sim = Simulation::new(StdRng::seed_from_u64(seed), system, 0.0).unwrap();
+    for _ in 0..steps {
+        sim.step().unwrap();
+    }
+    sim.set_rng(StdRng::seed_from_u64(swapped));
+    for _ in 0..steps {
+        sim.step().unwrap();
+    }
+    sim.inspect_model::<Roller>("roller").unwrap().0.clone()
+}
+
+#[test]
+fn swapped_rng_is_used_by_later_steps() {
+    let first = roll(1, 42, 3);
+    let second = roll(2, 42, 3);
+    assert_eq!(first.len(), 6);
+    assert_ne!(first[..3], second[..3]);
+    assert_eq!(first[3..], second[3..]);
+
+    let mut expected = StdRng::seed_from_u64(42);
+    let expected: Vec<u64> = (0..3).map(|_| expected.gen()).collect();
+    assert_eq!(first[3..], expected[..]);
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "rand")]
+
+use litesim::prelude::*;
+
+/// Model that routes its output back into its own input.
+struct Looper {
+    depth: usize,
+    max_depth: usize,
+    received: Vec<(Time, u32)>,
+}
+
+#[litesim_model]
+impl<'s> Model<'s> for Looper {
+    #[output]
+    fn output(&self, value: u32) -> _;
+
+    #[input]
+    fn input(&mut self, value: u32, ctx: ModelCtx<'s>) -> _ {
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+        self.received.push((ctx.time, value));
+        if value < 3 {
+            self.output(value + 1)?;
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn nested_delivery_is_queued() {
+    let mut system = SystemModel::new();
+    system.push_model(
+        "looper",
+        Looper {
+            depth: 0,
+            max_depth: 0,
+            received: vec![],
+        },
+    );
+    system.push_route(
+        ConnectorPath::new("looper", "output"),
+        ConnectorPath::new("looper", "input"),
+    );
+
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    sim.schedule_event(1.0, Event::new(0u32), ConnectorPath::new("looper", "input"))
+        .unwrap();
+    sim.run().unwrap();
+
+    let looper = sim.inspect_model::<Looper>("looper").unwrap();
+    assert_eq!(looper.max_depth, 1);
+    assert_eq!(
+        looper.received,
+        (0..=3).map(|it| (Time::at_secs(1.0), it)).collect::<Vec<_>>()
+    );
+}
+
+struct Recorder(Vec<u32>);
+
+#[litesim_model]
+impl<'s> Model<'s> for Recorder {
+    #[input]
+    fn input(&mut self, value: u32, _: ModelCtx<'s>) -> _ {
+        self.0.push(value);
+        Ok(())
+    }
+}
+
+#[test]
+fn same_time_entries_keep_scheduling_order() {
+    let mut system = SystemModel::new();
+    system.push_model("recorder", Recorder(vec![]));
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    for value in [3u32, 1, 4, 1, 5] {
+        let time = if value == 4 { 2.0 } else { 1.0 };
+        sim.schedule_event(time, Event::new(value), ConnectorPath::new("recorder", "input"))
+            .unwrap();
+    }
+    sim.run().unwrap();
+
+    assert_eq!(
+        sim.inspect_model::<Recorder>("recorder").unwrap().0,
+        vec![3, 1, 1, 5, 4]
+    );
+}
@@ -0,0 +1,95 @@
+#![cfg(all(feature = "serde", feature = "rand"))]
+
+use std::collections::HashMap;
+
+use litesim::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Counter {
+    count: u32,
+}
+
+#[litesim_model(state)]
+impl<'s> Model<'s> for Counter {
+    #[input(signal)]
+    fn increment(&mut self, _: ModelCtx<'s>) -> _ {
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// Model without state snapshot support.
+struct Stateless;
+
+#[litesim_model]
+impl<'s> Model<'s> for Stateless {}
+
+/// Model whose state can't be represented as JSON.
+#[derive(Serialize, Deserialize)]
+struct TupleKeys(HashMap<(u32, u32), u32>);
+
+#[litesim_model(state)]
+impl<'s> Model<'s> for TupleKeys {}
+
+fn simulation(count: u32) -> Simulation<'static> {
+    let mut system = SystemModel::new();
+    system.push_model("counter", Counter { count });
+    system.push_model("stateless", Stateless);
+    Simulation::new(rand::thread_rng(), system, 0.0).unwrap()
+}
+
+#[test]
+fn state_round_trip() {
+    let mut sim = simulation(0);
+    for time in 0..3 {
+        sim.schedule_event(time as f32, Event::new(()), ConnectorPath::new("counter", "increment"))
+            .unwrap();
+    }
+    sim.run().unwrap();
+
+    let states = sim.model_states().unwrap();
+    assert_eq!(states.len(), 1);
+    assert_eq!(states["counter"], serde_json::json!({ "count": 3 }));
+
+    let mut restored = simulation(0);
+    restored.load_model_states(states).unwrap();
+    assert_eq!(restored.inspect_model::<Counter>("counter").unwrap().count, 3);
+}
+
+#[test]
+fn unsupported_and_invalid_states_are_reported() {
+    let mut sim = simulation(0);
+
+    let invalid = HashMap::from([("counter".to_string(), serde_json::json!("three"))]);
+    assert!(matches!(
+        sim.load_model_states(invalid),
+        Err(SimulationError::State {
+            source: StateError::Invalid(_),
+            ..
+        })
+    ));
+
+    let unsupported = HashMap::from([("stateless".to_string(), serde_json::json!(null))]);
+    assert!(matches!(
+        sim.load_model_states(unsupported),
+        Err(SimulationError::State {
+            source: StateError::Unsupported,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn serialization_errors_are_returned() {
+    let mut system = SystemModel::new();
+    system.push_model("keys", TupleKeys(HashMap::from([((1, 2), 3)])));
+    let sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    assert!(matches!(
+        sim.model_states(),
+        Err(SimulationError::State {
+            source: StateError::Invalid(_),
+            ..
+        })
+    ));
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "stats")]
+
+use litesim::prelude::*;
+
+const VALUES: [f64; 8] = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+
+fn assert_close(actual: Option<f64>, expected: f64) {
+    let actual = actual.unwrap();
+    assert!(
+        (actual - expected).abs() < 1e-12,
+        "{} isn't close to {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn online_stats_known_values() {
+    let stats: OnlineStats = VALUES.into_iter().collect();
+    assert_eq!(stats.count(), 8);
+    assert_close(stats.mean(), 5.0);
+    assert_close(stats.variance(), 4.0);
+    assert_close(stats.sample_variance(), 32.0 / 7.0);
+    assert_close(stats.std_dev(), 2.0);
+    assert_eq!(stats.min(), Some(2.0));
+    assert_eq!(stats.max(), Some(9.0));
+}
+
+#[test]
+fn online_stats_empty_and_single() {
+    let mut stats = OnlineStats::new();
+    assert_eq!(stats.count(), 0);
+    assert_eq!(stats.mean(), None);
+    assert_eq!(stats.variance(), None);
+    assert_eq!(stats.min(), None);
+
+    stats.push(3.0);
+    assert_eq!(stats.mean(), Some(3.0));
+    assert_eq!(stats.variance(), Some(0.0));
+    assert_eq!(stats.sample_variance(), None);
+}
+
+#[test]
+fn merged_stats_match_single_accumulator() {
+    let all: OnlineStats = VALUES.into_iter().collect();
+    for split in 0..=VALUES.len() {
+        let mut merged: OnlineStats = VALUES[..split].iter().copied().collect();
+        let rest: OnlineStats = VALUES[split..].iter().copied().collect();
+        merged.merge(&rest);
+
+        assert_eq!(merged.count(), all.count());
+        assert_close(merged.mean(), all.mean().unwrap());
+        assert_close(merged.variance(), all.variance().unwrap());
+        assert_close(merged.sample_variance(), all.sample_variance().unwrap());
+        assert_eq!(merged.min(), all.min());
+        assert_eq!(merged.max(), all.max());
+    }
+}
+
+#[test]
+fn histogram_counts_bins_and_outliers() {
+    let mut histogram = Histogram::new(0.0..1.0, 4);
+    histogram.extend([0.0, 0.1, 0.25, 0.5, 0.99, -0.5, 1.0, 2.0, f64::NAN]);
+
+    assert_eq!(histogram.counts(), &[2, 1, 1, 1]);
+    assert_eq!(histogram.underflow(), 1);
+    assert_eq!(histogram.overflow(), 2);
+    assert_eq!(histogram.total(), 8);
+
+    let bins: Vec<_> = histogram.bins().collect();
+    assert_eq!(bins[0], (0.0..0.25, 2));
+    assert_eq!(bins[3], (0.75..1.0, 1));
+}
+
+#[test]
+fn histogram_value_below_end_lands_in_last_bin() {
+    let mut histogram = Histogram::new(0.0..0.9, 3);
+    let below_end = 0.9f64.next_down();
+    // division rounds up to the bin past the last one
+    assert_eq!((below_end / histogram.bin_width()) as usize, 3);
+
+    histogram.push(below_end);
+    assert_eq!(histogram.counts(), &[0, 0, 1]);
+    assert_eq!(histogram.overflow(), 0);
+}
+
+#[test]
+#[should_panic]
+fn histogram_rejects_empty_range() {
+    Histogram::new(1.0..1.0, 4);
+}
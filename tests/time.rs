@@ -0,0 +1,192 @@
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+
+use litesim::prelude::*;
+
+fn secs(value: f64) -> TimeDelta {
+    TimeDelta::from_secs_f64(value)
+}
+
+#[test]
+fn time_delta_min_max() {
+    let (short, long) = (secs(1.5), secs(4.0));
+    assert_eq!(short.min(long), short);
+    assert_eq!(long.min(short), short);
+    assert_eq!(short.max(long), long);
+    assert_eq!(long.max(short), long);
+    assert_eq!(short.min(short), short);
+
+    let negative = secs(-2.0);
+    assert_eq!(negative.min(TimeDelta::ZERO), negative);
+    assert_eq!(negative.max(TimeDelta::ZERO), TimeDelta::ZERO);
+    assert_eq!(long.max(TimeDelta::MAX), TimeDelta::MAX);
+}
+
+#[test]
+fn time_delta_ordering() {
+    let mut deltas = vec![secs(3.0), secs(-1.0), TimeDelta::ZERO, secs(0.25)];
+    deltas.sort();
+    assert_eq!(deltas, vec![secs(-1.0), TimeDelta::ZERO, secs(0.25), secs(3.0)]);
+    assert_eq!(deltas.iter().max(), Some(&secs(3.0)));
+    assert!(TimeDelta::from_millis(999) < secs(1.0));
+    assert_eq!(secs(5.0).clamp(secs(1.0), secs(2.0)), secs(2.0));
+}
+
+fn inc(secs: f64) -> Bound<Time> {
+    Included(Time::at_secs(secs))
+}
+
+fn exc(secs: f64) -> Bound<Time> {
+    Excluded(Time::at_secs(secs))
+}
+
+fn bounds(start: Bound<Time>, end: Bound<Time>) -> TimeBounds {
+    TimeBounds { start, end }
+}
+
+#[test]
+fn time_bounds_is_empty() {
+    let cases = [
+        (bounds(inc(1.0), inc(1.0)), false),
+        (bounds(inc(1.0), exc(1.0)), true),
+        (bounds(exc(1.0), inc(1.0)), true),
+        (bounds(exc(1.0), exc(1.0)), true),
+        (bounds(exc(1.0), exc(2.0)), false),
+        (bounds(inc(2.0), inc(1.0)), true),
+        (bounds(Unbounded, exc(1.0)), false),
+        (bounds(exc(1.0), Unbounded), false),
+        (bounds(Unbounded, Unbounded), false),
+    ];
+    for (bounds, empty) in cases {
+        assert_eq!(bounds.is_empty(), empty, "{:?}", bounds);
+    }
+}
+
+#[test]
+fn time_bounds_intersect() {
+    let cases = [
+        (
+            bounds(inc(0.0), exc(2.0)),
+            bounds(inc(1.0), exc(3.0)),
+            Some(bounds(inc(1.0), exc(2.0))),
+        ),
+        (bounds(inc(0.0), exc(1.0)), bounds(inc(1.0), exc(2.0)), None),
+        (
+            bounds(inc(0.0), inc(1.0)),
+            bounds(inc(1.0), exc(2.0)),
+            Some(bounds(inc(1.0), inc(1.0))),
+        ),
+        (bounds(inc(0.0), inc(1.0)), bounds(exc(1.0), exc(2.0)), None),
+        (
+            bounds(inc(0.0), inc(2.0)),
+            bounds(exc(0.0), exc(2.0)),
+            Some(bounds(exc(0.0), exc(2.0))),
+        ),
+        (
+            bounds(inc(0.0), exc(2.0)),
+            bounds(inc(0.0), inc(2.0)),
+            Some(bounds(inc(0.0), exc(2.0))),
+        ),
+        (
+            bounds(Unbounded, Unbounded),
+            bounds(inc(1.0), exc(2.0)),
+            Some(bounds(inc(1.0), exc(2.0))),
+        ),
+        (
+            bounds(Unbounded, inc(1.0)),
+            bounds(inc(1.0), Unbounded),
+            Some(bounds(inc(1.0), inc(1.0))),
+        ),
+        (bounds(Unbounded, exc(1.0)), bounds(inc(1.0), Unbounded), None),
+        (bounds(inc(2.0), inc(3.0)), bounds(inc(0.0), inc(1.0)), None),
+    ];
+    for (a, b, expected) in cases {
+        assert_eq!(a.intersect(&b), expected, "{:?} & {:?}", a, b);
+        assert_eq!(b.intersect(&a), expected, "{:?} & {:?}", b, a);
+    }
+}
+
+#[test]
+fn time_bounds_contains_bounds() {
+    let cases = [
+        (bounds(Unbounded, Unbounded), bounds(inc(0.0), inc(1.0)), true),
+        (bounds(inc(0.0), inc(1.0)), bounds(Unbounded, Unbounded), false),
+        (bounds(inc(0.0), inc(2.0)), bounds(inc(0.0), exc(2.0)), true),
+        (bounds(inc(0.0), exc(2.0)), bounds(inc(0.0), inc(2.0)), false),
+        (bounds(inc(0.0), exc(2.0)), bounds(exc(0.0), exc(2.0)), true),
+        (bounds(exc(0.0), exc(2.0)), bounds(inc(0.0), inc(1.0)), false),
+        (bounds(inc(0.0), Unbounded), bounds(inc(1.0), Unbounded), true),
+        (bounds(inc(1.0), Unbounded), bounds(inc(0.0), Unbounded), false),
+        (bounds(Unbounded, inc(2.0)), bounds(Unbounded, exc(2.0)), true),
+        (bounds(Unbounded, exc(2.0)), bounds(Unbounded, inc(2.0)), false),
+        (bounds(inc(5.0), inc(6.0)), bounds(exc(1.0), exc(1.0)), true),
+    ];
+    for (outer, inner, contains) in cases {
+        assert_eq!(
+            outer.contains_bounds(&inner),
+            contains,
+            "{:?} contains {:?}",
+            outer,
+            inner
+        );
+    }
+}
+
+#[cfg(any(feature = "time_f32", feature = "time_f64"))]
+#[test]
+fn nan_time_is_invalid() {
+    assert!(Time::at_secs(1.0).is_valid());
+    assert!(!Time::at_secs(f64::NAN).is_valid());
+    assert!(!Time::at_secs(f64::NEG_INFINITY).is_valid());
+}
+
+#[cfg(any(feature = "time_f32", feature = "time_f64"))]
+struct NanDelay;
+
+#[cfg(any(feature = "time_f32", feature = "time_f64"))]
+#[litesim_model]
+impl<'s> Model<'s> for NanDelay {
+    #[input(signal)]
+    fn start(&mut self, ctx: ModelCtx<'s>) -> _ {
+        ctx.schedule_update(In(TimeDelta::from_secs_f64(f64::NAN)))
+    }
+}
+
+#[cfg(all(feature = "rand", any(feature = "time_f32", feature = "time_f64")))]
+#[test]
+fn nan_trigger_is_rejected() {
+    use litesim::error::SchedulerError;
+
+    let mut system = SystemModel::new();
+    system.push_model("model", NanDelay);
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+
+    let scheduled = sim.schedule_event(
+        Time::at_secs(f64::NAN),
+        Event::new(()),
+        ConnectorPath::new("model", "start"),
+    );
+    assert!(matches!(scheduled, Err(SchedulerError::InvalidTime(_))));
+
+    sim.schedule_event(0.0, Event::new(()), ConnectorPath::new("model", "start"))
+        .unwrap();
+    assert!(matches!(
+        sim.run(),
+        Err(SimulationError::Scheduler(SchedulerError::InvalidTime(_)))
+    ));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn invalid_realtime_scale_is_rejected() {
+    use std::sync::atomic::AtomicBool;
+
+    let mut sim = Simulation::new(rand::thread_rng(), SystemModel::new(), 0.0).unwrap();
+    let stop = AtomicBool::new(false);
+    for scale in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            sim.run_realtime(scale, &stop),
+            Err(SimulationError::InvalidRealtimeScale { .. })
+        ));
+    }
+    assert!(sim.run_realtime(1.0, &stop).is_ok());
+}
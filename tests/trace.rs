@@ -0,0 +1,156 @@
+#![cfg(feature = "rand")]
+
+use std::{cell::RefCell, rc::Rc};
+
+use litesim::prelude::*;
+
+/// Sends the ball back one second after receiving it.
+struct Player;
+
+#[litesim_model]
+impl<'s> Model<'s> for Player {
+    #[input(signal)]
+    fn receive(&mut self, ctx: ModelCtx<'s>) -> _ {
+        ctx.schedule_update(Now)?;
+        Ok(())
+    }
+
+    #[output(signal)]
+    fn send(&self) -> _;
+
+    fn handle_update(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        self.send(In(TimeDelta::from_secs_f64(1.0)))?;
+        Ok(())
+    }
+}
+
+fn ping_pong(recorders: &[Rc<RefCell<TraceRecorder<'static>>>]) {
+    let mut system = SystemModel::new();
+    system.push_model("p1", Player);
+    system.push_model("p2", Player);
+    system.push_route(connection!(p1::send), connection!(p2::receive));
+    system.push_route(connection!(p2::send), connection!(p1::receive));
+
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    for recorder in recorders {
+        sim.add_observer(recorder.clone());
+    }
+    sim.schedule_event(0.0, Signal(), connection!(p1::receive))
+        .unwrap();
+    sim.run_until(9.5).unwrap();
+}
+
+fn recorder(recorder: TraceRecorder<'static>) -> Rc<RefCell<TraceRecorder<'static>>> {
+    Rc::new(RefCell::new(recorder))
+}
+
+#[test]
+fn recorder_counts_routes_and_updates() {
+    let all = recorder(TraceRecorder::new());
+    ping_pong(std::slice::from_ref(&all));
+    let all = all.borrow();
+
+    let serve = Route::new_external(connection!(p1::receive));
+    let forward = Route::new(connection!(p1::send), connection!(p2::receive));
+    let back = Route::new(connection!(p2::send), connection!(p1::receive));
+    assert_eq!(all.count_route(&serve), 1);
+    assert_eq!(all.count_route(&forward), 5);
+    assert_eq!(all.count_route(&back), 4);
+    assert_eq!(all.count_updates("p1"), 5);
+    assert_eq!(all.count_updates("p2"), 5);
+    assert_eq!(all.events().count(), 10);
+    assert_eq!(all.len(), 20);
+}
+
+#[test]
+fn recorder_filters() {
+    let p2 = recorder(TraceRecorder::new().with_model("p2"));
+    let send = recorder(TraceRecorder::new().with_connector("send"));
+    let p2_receive = recorder(TraceRecorder::new().with_model("p2").with_connector("receive"));
+    let both = recorder(TraceRecorder::new().with_model("p1").with_model("p2"));
+    ping_pong(&[p2.clone(), send.clone(), p2_receive.clone(), both.clone()]);
+
+    let serve = Route::new_external(connection!(p1::receive));
+
+    // events from or to p2 and its updates
+    let p2 = p2.borrow();
+    assert_eq!(p2.count_route(&serve), 0);
+    assert_eq!(p2.events().count(), 9);
+    assert_eq!(p2.count_updates("p1"), 0);
+    assert_eq!(p2.count_updates("p2"), 5);
+
+    // connector filters skip updates
+    let send = send.borrow();
+    assert_eq!(send.count_route(&serve), 0);
+    assert_eq!(send.events().count(), 9);
+    assert_eq!(send.len(), 9);
+
+    // model and connector filters must both match; serve is delivered to
+    // `receive`, but not to p2
+    let p2_receive = p2_receive.borrow();
+    assert_eq!(p2_receive.count_route(&serve), 0);
+    assert_eq!(p2_receive.events().count(), 9);
+    assert_eq!(p2_receive.len(), 9);
+
+    // repeated filters of the same kind match any of them
+    assert_eq!(both.borrow().len(), 20);
+}
+
+/// Forwards received pairs on its next update.
+struct Echo(Option<(u32, u32)>);
+
+#[litesim_model]
+impl<'s> Model<'s> for Echo {
+    #[input]
+    fn input(&mut self, value: (u32, u32), ctx: ModelCtx<'s>) -> _ {
+        self.0 = Some(value);
+        ctx.schedule_update(Now)?;
+        Ok(())
+    }
+
+    #[output]
+    fn output(&self, value: (u32, u32)) -> _;
+
+    fn handle_update(&mut self, _: ModelCtx<'s>) -> Result<(), SimulationError> {
+        if let Some(value) = self.0.take() {
+            self.output(value)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn recorder_writes_csv() {
+    let mut system = SystemModel::new();
+    system.push_model("echo, \"1\"", Echo(None));
+    system.push_model("echo2", Echo(None));
+    system.push_route(
+        ConnectorPath::new("echo, \"1\"", "output"),
+        ConnectorPath::new("echo2", "input"),
+    );
+    let trace = recorder(TraceRecorder::new().with_model("echo, \"1\""));
+
+    let mut sim = Simulation::new(rand::thread_rng(), system, 0.0).unwrap();
+    sim.add_observer(trace.clone());
+    sim.schedule_event(
+        1.0,
+        Event::new((1u32, 2u32)),
+        ConnectorPath::new("echo, \"1\"", "input"),
+    )
+    .unwrap();
+    sim.run().unwrap();
+
+    let mut csv = Vec::new();
+    trace.borrow().to_csv(&mut csv).unwrap();
+    let time = Time::at_secs(1.0).to_string();
+    let expected = [
+        "time,from_model,from_connector,to_model,to_connector,event_type,kind".to_string(),
+        format!("{},,,\"echo, \"\"1\"\"\",input,\"(u32, u32)\",external", time),
+        format!("{},,,\"echo, \"\"1\"\"\",,,update", time),
+        format!(
+            "{},\"echo, \"\"1\"\"\",output,echo2,input,\"(u32, u32)\",event",
+            time
+        ),
+    ];
+    assert_eq!(String::from_utf8(csv).unwrap().lines().collect::<Vec<_>>(), expected);
+}